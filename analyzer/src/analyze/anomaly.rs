@@ -0,0 +1,239 @@
+use super::config::AnalysisConfig;
+use crate::data::Invite;
+use chrono::{Datelike, Days, Weekday};
+use serde::Serialize;
+
+// the fewest recent draws of the category the size and the cutoff are compared with
+const MIN_DRAWS: usize = 3;
+// the fewest recent draws of the category the weekday is compared with
+const MIN_WEEKDAY_DRAWS: usize = 5;
+// the share of the recent draws below which a weekday is unusual
+const WEEKDAY_SHARE: f64 = 0.1;
+// the smallest distance from the recent cutoffs flagged, in points
+const CUTOFF_MARGIN: f64 = 20.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AnomalyKind {
+    /// half or twice the median size of the recent draws of the category, or beyond.
+    Size,
+    /// beyond the range of the recent cutoffs of the category by a margin of half the range,
+    /// and at least 20 points.
+    Cutoff,
+    /// on a weekday fewer than a tenth of the recent draws of the category were held on.
+    Weekday,
+    /// the first draw of the category.
+    FirstDraw,
+}
+
+impl AnomalyKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Size => "size",
+            Self::Cutoff => "cutoff",
+            Self::Weekday => "weekday",
+            Self::FirstDraw => "first draw",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Anomaly {
+    pub kind: AnomalyKind,
+    /// how far the draw is from the usual ones, 1 at the threshold it is flagged from.
+    pub score: f64,
+    pub explanation: String,
+}
+
+/// A draw with at least one anomaly.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlaggedDraw<'a> {
+    pub invite: &'a Invite,
+    pub anomalies: Vec<Anomaly>,
+}
+
+pub struct AnomalyAnalyzer;
+
+impl AnomalyAnalyzer {
+    /// the anomalies of the draw against the draws before it, the draws of its category within
+    /// the lookbehind window for the size, the cutoff and the weekday. None for the first draw of
+    /// all.
+    pub fn anomalies(
+        config: &AnalysisConfig,
+        previous: &[Invite],
+        invite: &Invite,
+    ) -> Vec<Anomaly> {
+        if previous.is_empty() {
+            return Vec::new();
+        }
+        let category = invite.category.code.as_str();
+        let history: Vec<_> = previous
+            .iter()
+            .filter(|x| x.category.code == invite.category.code)
+            .collect();
+        if history.is_empty() {
+            return vec![Anomaly {
+                kind: AnomalyKind::FirstDraw,
+                score: 1.0,
+                explanation: format!("no earlier {} draw", category),
+            }];
+        }
+
+        let since = invite.date - Days::new(config.lookbehind_days);
+        let recent: Vec<_> = history.iter().filter(|x| x.date > since).collect();
+        let of_recent = format!("the {} previous {} draws", recent.len(), category);
+        let mut anomalies = Vec::new();
+
+        if recent.len() >= MIN_DRAWS {
+            let mut sizes: Vec<_> = recent.iter().map(|x| x.size).collect();
+            sizes.sort_by(f64::total_cmp);
+            let median = sizes[sizes.len() / 2];
+            let ratio = invite.size / median;
+            let score = ratio.log2().abs();
+            if score >= 1.0 {
+                anomalies.push(Anomaly {
+                    kind: AnomalyKind::Size,
+                    score,
+                    explanation: format!(
+                        "{:.0} is {:.1} times the median {:.0} of {}",
+                        invite.size, ratio, median, of_recent
+                    ),
+                });
+            }
+
+            let min = recent.iter().map(|x| x.score).fold(f64::INFINITY, f64::min);
+            let max = recent
+                .iter()
+                .map(|x| x.score)
+                .fold(f64::NEG_INFINITY, f64::max);
+            let margin = CUTOFF_MARGIN.max((max - min) / 2.0);
+            let (distance, side) = if invite.score < min {
+                (min - invite.score, "below")
+            } else {
+                (invite.score - max, "above")
+            };
+            if distance >= margin {
+                anomalies.push(Anomaly {
+                    kind: AnomalyKind::Cutoff,
+                    score: distance / margin,
+                    explanation: format!(
+                        "{} is {} {} the range {} - {} of {}",
+                        invite.score, distance, side, min, max, of_recent
+                    ),
+                });
+            }
+        }
+
+        if recent.len() >= MIN_WEEKDAY_DRAWS {
+            let weekday = invite.date.weekday();
+            let same = recent
+                .iter()
+                .filter(|x| x.date.weekday() == weekday)
+                .count();
+            let share = same as f64 / recent.len() as f64;
+            if share < WEEKDAY_SHARE {
+                anomalies.push(Anomaly {
+                    kind: AnomalyKind::Weekday,
+                    score: (WEEKDAY_SHARE - share) / WEEKDAY_SHARE,
+                    explanation: format!(
+                        "a {}, as {} of {}",
+                        weekday_name(weekday),
+                        same,
+                        of_recent
+                    ),
+                });
+            }
+        }
+        anomalies
+    }
+
+    /// the draws with anomalies, each against the draws before it.
+    pub fn flagged<'a>(config: &AnalysisConfig, invite_data: &'a [Invite]) -> Vec<FlaggedDraw<'a>> {
+        invite_data
+            .iter()
+            .enumerate()
+            .map(|(i, invite)| FlaggedDraw {
+                invite,
+                anomalies: Self::anomalies(config, &invite_data[..i], invite),
+            })
+            .filter(|draw| !draw.anomalies.is_empty())
+            .collect()
+    }
+}
+
+fn weekday_name(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "Monday",
+        Weekday::Tue => "Tuesday",
+        Weekday::Wed => "Wednesday",
+        Weekday::Thu => "Thursday",
+        Weekday::Fri => "Friday",
+        Weekday::Sat => "Saturday",
+        Weekday::Sun => "Sunday",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AnomalyAnalyzer, AnomalyKind};
+    use crate::analyze::config::AnalysisConfig;
+    use crate::data::test_util::invites;
+    use crate::data::InviteId;
+    use chrono::{Days, NaiveDate};
+
+    #[tokio::test]
+    async fn flagged() {
+        // Wednesdays
+        let first = NaiveDate::from_ymd_opt(2024, 1, 3).unwrap();
+        let mut draws: Vec<_> = (0..6)
+            .map(|i| {
+                let date = first + Days::new(14 * i);
+                (date, "General", 1000.0 + 100.0 * i as f64, 520.0 + i as f64)
+            })
+            .collect();
+        draws.push((first + Days::new(85), "General", 3000.0, 524.0));
+        draws.push((first + Days::new(98), "General", 1500.0, 480.0));
+        let stem = "STEM occupations (2024-1)";
+        draws.push((first + Days::new(100), stem, 500.0, 490.0));
+        let invite_data = invites(&draws);
+
+        let config = AnalysisConfig::DEFAULT;
+        let flagged = AnomalyAnalyzer::flagged(&config, &invite_data);
+        let flagged: Vec<_> = flagged
+            .iter()
+            .map(|draw| {
+                let kinds: Vec<_> = draw.anomalies.iter().map(|x| x.kind).collect();
+                (draw.invite.id, kinds)
+            })
+            .collect();
+        assert_eq!(
+            flagged,
+            vec![
+                // a Thursday, more than twice the median
+                (
+                    InviteId::new(306, 0),
+                    vec![AnomalyKind::Size, AnomalyKind::Weekday]
+                ),
+                (InviteId::new(307, 0), vec![AnomalyKind::Cutoff]),
+                (InviteId::new(308, 0), vec![AnomalyKind::FirstDraw]),
+            ]
+        );
+
+        let anomalies = AnomalyAnalyzer::anomalies(&config, &invite_data[..7], &invite_data[7]);
+        assert_eq!(
+            anomalies[0].explanation,
+            "480 is 40 below the range 520 - 525 of the 7 previous General draws"
+        );
+        assert_eq!(anomalies[0].score, 2.0);
+        let anomalies = AnomalyAnalyzer::anomalies(&config, &invite_data[..6], &invite_data[6]);
+        assert_eq!(
+            anomalies[0].explanation,
+            "3000 is 2.3 times the median 1300 of the 6 previous General draws"
+        );
+        assert_eq!(
+            anomalies[1].explanation,
+            "a Thursday, as 0 of the 6 previous General draws"
+        );
+    }
+}
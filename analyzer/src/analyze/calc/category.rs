@@ -26,13 +26,6 @@ impl CategoryPool {
         }
         self
     }
-
-    pub fn max(mut self, other: Self) -> Self {
-        for i in 0..Self::N {
-            self[i] = f64::max(self[i], other[i])
-        }
-        self
-    }
 }
 
 impl Default for CategoryPool {
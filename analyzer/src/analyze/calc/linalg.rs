@@ -0,0 +1,57 @@
+// the smallest pivot of the scaled matrix, below which it is taken as singular
+const EPSILON: f64 = 1e-10;
+
+/// Gaussian elimination with partial pivoting on an augmented matrix `(A | b)`, None if `A` is
+/// singular. The columns of `A` are scaled to a unit norm first, so that the singularity does
+/// not depend on their magnitude and near collinear columns are told apart from small ones.
+pub fn solve(mut a: Vec<Vec<f64>>) -> Option<Vec<f64>> {
+    let n = a.len();
+    let scales: Vec<_> = (0..n)
+        .map(|c| a.iter().map(|row| row[c].powi(2)).sum::<f64>().sqrt())
+        .collect();
+    if scales.contains(&0.0) {
+        return None;
+    }
+    for row in a.iter_mut() {
+        for (x, scale) in row.iter_mut().zip(&scales) {
+            *x /= scale;
+        }
+    }
+
+    for i in 0..n {
+        let pivot = (i..n).max_by(|x, y| a[*x][i].abs().total_cmp(&a[*y][i].abs()))?;
+        if a[pivot][i].abs() < EPSILON {
+            return None;
+        }
+        a.swap(i, pivot);
+        let pivot_row = a[i].clone();
+        for (r, row) in a.iter_mut().enumerate() {
+            if r != i {
+                let factor = row[i] / pivot_row[i];
+                for (x, p) in row.iter_mut().zip(&pivot_row).skip(i) {
+                    *x -= factor * p;
+                }
+            }
+        }
+    }
+    Some((0..n).map(|i| a[i][n] / a[i][i] / scales[i]).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::solve;
+
+    #[tokio::test]
+    async fn solve_scaled() {
+        // x + 2y = 5, 3x + 4y = 11, with the second column a million times larger
+        let x = solve(vec![vec![1.0, 2e6, 5.0], vec![3.0, 4e6, 11.0]]).unwrap();
+        assert!((x[0] - 1.0).abs() < 1e-9 && (x[1] - 2e-6).abs() < 1e-15);
+        // near collinear columns of large values, their pivot is far above an absolute 1e-12
+        let a = vec![
+            vec![1e12, 1e12 + 1e-2, 1.0],
+            vec![1e12 + 1e-2, 1e12 + 2e-2, 2.0],
+        ];
+        assert_eq!(solve(a), None);
+        assert_eq!(solve(vec![vec![0.0, 1.0]]), None);
+    }
+}
@@ -1,5 +1,7 @@
 mod score;
 mod category;
+mod linalg;
 
 pub use score::ScorePool;
 pub use category::CategoryPool;
+pub use linalg::solve;
@@ -166,19 +166,17 @@ impl ScorePool {
 }
 
 impl ScorePool {
-    pub fn pnp(self) -> Self {
-        let mut pool = Self::default();
-        pool[14] = self[14]; // 14-th bucket is 601 to 1200
-        pool
+    pub fn pnp(self, pnp_score: f64) -> Self {
+        self.within_score(pnp_score, 1200.0)
     }
 
-    pub fn non_pnp(self) -> Self {
-        self - self.pnp()
+    pub fn non_pnp(self, pnp_score: f64) -> Self {
+        self - self.pnp(pnp_score)
     }
 
-    pub fn invite(mut self, _invite: &data::Invite) -> Self {
+    pub fn invite(mut self, _invite: &data::Invite, pnp_score: f64) -> Self {
         if !_invite.pathway.is_pnp() {
-            self = self.non_pnp();
+            self = self.non_pnp(pnp_score);
         }
 
         if _invite.category.code == CategoryCode::General {
@@ -210,6 +208,12 @@ impl ScorePool {
         let multiplier = self.multiplier_within_score(min_score, max_score);
         self * multiplier
     }
+
+    /// the pool left once its `count` best candidates are invited, as by a general draw.
+    pub fn without_best(self, count: f64) -> Self {
+        let multiplier = self.multiplier_invite_general(count);
+        self - self * multiplier
+    }
 }
 
 #[cfg(test)]
@@ -222,7 +226,7 @@ mod tests {
             1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 15.0,
         ]);
 
-        let res0 = pool.non_pnp();
+        let res0 = pool.non_pnp(600.0);
         assert_eq!(
             res0.0,
             [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 0.0]
@@ -241,7 +245,8 @@ mod tests {
             [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 4.0, 11.0, 12.0, 13.0, 14.0, 15.0]
         );
 
-        let res1 = pool.non_pnp().multiplier_invite_general(69.0) * pool.non_pnp();
+        let non_pnp = pool.non_pnp(600.0);
+        let res1 = non_pnp.multiplier_invite_general(69.0) * non_pnp;
         assert_eq!(
             res1.0,
             [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 9.0, 10.0, 11.0, 12.0, 13.0, 14.0, 0.0]
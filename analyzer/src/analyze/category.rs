@@ -1,8 +1,27 @@
 use super::calc::{CategoryPool, ScorePool};
-use crate::data::{CategoryCode, Invite, Pool};
+use super::config::AnalysisConfig;
+use crate::data::{CategoryCode, DateRange, Invite, Plan, Pool};
 use chrono::{Days, NaiveDate};
+use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
 
+/// The invitations of a category within the year of a plan against its target.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CategoryProgress {
+    pub category: CategoryCode,
+    pub invited: f64,
+    pub target: Option<f64>,
+}
+
+impl CategoryProgress {
+    /// share of the target already invited, `None` without a target.
+    pub fn ratio(&self) -> Option<f64> {
+        self.target
+            .filter(|target| *target > 0.0)
+            .map(|target| self.invited / target)
+    }
+}
+
 pub struct CategoryAnalyzer;
 
 impl CategoryAnalyzer {
@@ -42,6 +61,7 @@ impl CategoryAnalyzer {
     }
 
     pub fn invite_per_category(
+        config: &AnalysisConfig,
         pool_data: &[Pool],
         invite_data: &[Invite],
     ) -> (Vec<NaiveDate>, Vec<CategoryPool>, HashSet<CategoryCode>) {
@@ -90,7 +110,7 @@ impl CategoryAnalyzer {
                 }
 
                 let invite_as_pool = if let Some(pool) = pool_to_invite {
-                    let invite_as_pool = pool.invite(invite);
+                    let invite_as_pool = pool.invite(invite, config.pnp_score);
                     pool_to_invite = Some(pool - invite_as_pool); // remove already invited candidates from the pool to avoid duplicate counts.
                     Some(invite_as_pool)
                 } else {
@@ -99,8 +119,10 @@ impl CategoryAnalyzer {
 
                 if invite.category.code == CategoryCode::General {
                     if let Some(invite_as_pool) = invite_as_pool {
-                        value[CategoryCode::Province] += invite_as_pool.pnp().total();
-                        value[CategoryCode::General] += invite_as_pool.non_pnp().total();
+                        value[CategoryCode::Province] +=
+                            invite_as_pool.pnp(config.pnp_score).total();
+                        value[CategoryCode::General] +=
+                            invite_as_pool.non_pnp(config.pnp_score).total();
                         categories.insert(CategoryCode::Province);
                         categories.insert(CategoryCode::General);
                     } else {
@@ -125,6 +147,7 @@ impl CategoryAnalyzer {
     }
 
     pub fn percent_per_category(
+        config: &AnalysisConfig,
         pool_data: &[Pool],
         invite_data: &[Invite],
     ) -> (Vec<NaiveDate>, Vec<CategoryPool>, HashSet<CategoryCode>) {
@@ -174,14 +197,14 @@ impl CategoryAnalyzer {
                 }
 
                 if let Some(pool) = pool_to_invite {
-                    let invite_as_pool = pool.invite(invite);
+                    let invite_as_pool = pool.invite(invite, config.pnp_score);
                     pool_to_invite = Some(pool - invite_as_pool); // remove already invited candidates from the pool to avoid duplicate counts.
 
                     if invite.category.code != CategoryCode::General
                         && invite.category.code != CategoryCode::Province
                     {
                         value[invite.category.code] =
-                            invite.size / pool.within_score(invite.score, 600.0).total();
+                            invite.size / pool.within_score(invite.score, config.pnp_score).total();
                         categories.insert(invite.category.code);
                     }
                 }
@@ -196,13 +219,73 @@ impl CategoryAnalyzer {
 
         (labels, values, categories)
     }
+
+    /// the cumulative invitations per category at each draw date of the year of the plan,
+    /// with the progress of the categories invited in the year, drawn in its category round
+    /// or with a target.
+    pub fn progress_per_category(
+        config: &AnalysisConfig,
+        pool_data: &[Pool],
+        invite_data: &[Invite],
+        plan: &Plan,
+    ) -> (Vec<NaiveDate>, Vec<CategoryPool>, Vec<CategoryProgress>) {
+        let year = DateRange::new(
+            NaiveDate::from_yo_opt(plan.year, 1),
+            NaiveDate::from_yo_opt(plan.year + 1, 1).and_then(|date| date.pred_opt()),
+        );
+        // the invitations are walked from the pool in effect at the start of the year,
+        // the ones before are then taken out of the cumulative counts
+        let warm_up = year.since_prior_pool(pool_data);
+        let (mut labels, mut values, mut categories) = Self::invite_per_category(
+            config,
+            warm_up.pools(pool_data),
+            warm_up.invites(invite_data),
+        );
+        let skip = labels.partition_point(|date| !year.contains(*date));
+        if skip > 0 {
+            let earlier = values[skip - 1];
+            labels.drain(..skip);
+            values = values.drain(skip..).map(|pool| pool - earlier).collect();
+        }
+
+        let last = values.last().copied().unwrap_or_default();
+        categories.retain(|category| last[*category] != 0.0);
+        if let Some(round) = Self::of_category_years(invite_data).get(&plan.year) {
+            categories.extend(
+                round
+                    .iter()
+                    .filter(|invite| invite.category.year == Some(plan.year))
+                    .map(|invite| invite.category.code),
+            );
+        }
+        categories.extend(
+            CategoryCode::values()
+                .iter()
+                .filter(|category| plan.target(**category).is_some()),
+        );
+
+        let progress = categories
+            .into_iter()
+            .sorted_by_key(|category| *category as usize)
+            .map(|category| CategoryProgress {
+                category,
+                invited: last[category],
+                target: plan.target(category),
+            })
+            .collect();
+
+        (labels, values, progress)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::CategoryAnalyzer;
+    use crate::analyze::config::AnalysisConfig;
+    use crate::data::test_util::{invites, pools};
     use crate::data::wasm_invite_data;
-    use crate::data::InviteId;
+    use crate::data::{CategoryCode, InviteId, Plan};
+    use chrono::NaiveDate;
 
     #[tokio::test]
     async fn of_category_years() {
@@ -217,4 +300,45 @@ mod tests {
 
         assert_eq!(map[&2024].first().unwrap().id, InviteId::parse("287"));
     }
+
+    #[tokio::test]
+    async fn progress_per_category() {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        let french = |year| format!("French language proficiency ({}-1)", year);
+        let stem = "STEM occupations (2023-1)";
+        let invite_data = invites(&[
+            (date(2023, 12, 20), stem, 1000.0, 450.0),
+            (date(2024, 2, 1), &french(2024), 500.0, 450.0),
+            (date(2024, 3, 1), "General", 2000.0, 450.0),
+            (date(2025, 1, 10), &french(2025), 700.0, 450.0),
+        ]);
+        let pool_data = pools(&[(date(2023, 12, 1), 10000.0)]);
+        let plan = Plan::new(2024, 90000.0, 116000.0)
+            .with_target(CategoryCode::French, 1500.0)
+            .with_target(CategoryCode::Health, 3000.0);
+
+        let (labels, values, progress) = CategoryAnalyzer::progress_per_category(
+            &AnalysisConfig::DEFAULT,
+            &pool_data,
+            &invite_data,
+            &plan,
+        );
+        assert_eq!(labels, vec![date(2024, 2, 1), date(2024, 3, 1)]);
+        assert_eq!(values[0][CategoryCode::French], 500.0);
+        assert_eq!(values[1][CategoryCode::Stem], 0.0);
+
+        let categories: Vec<_> = progress.iter().map(|x| x.category).collect();
+        assert_eq!(
+            categories,
+            vec![
+                CategoryCode::General,
+                CategoryCode::Health,
+                CategoryCode::French
+            ]
+        );
+        assert_eq!(progress[0].invited.round(), 2000.0);
+        assert_eq!(progress[0].ratio(), None);
+        assert_eq!(progress[1].invited, 0.0);
+        assert_eq!(progress[2].ratio(), Some(500.0 / 1500.0));
+    }
 }
@@ -0,0 +1,220 @@
+use super::calc::ScorePool;
+use super::config::AnalysisConfig;
+use super::eligibility::EligibilityAnalyzer;
+use crate::data::{CategoryCode, DateRange, Invite, Pool};
+use chrono::{Days, NaiveDate};
+use std::f64::consts::LN_2;
+
+/// How much an estimate can be trusted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Confidence {
+    Low,
+    Medium,
+    High,
+}
+
+impl Confidence {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+
+    fn lower(self) -> Self {
+        match self {
+            Self::High => Self::Medium,
+            Self::Medium | Self::Low => Self::Low,
+        }
+    }
+}
+
+/// The share of the candidates above its cutoff a category draw took, measured on the pool
+/// distributions on either side of it, against the share its size tells.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Drain {
+    pub category: CategoryCode,
+    pub date: NaiveDate,
+    pub measured: f64,
+    pub expected: f64,
+}
+
+impl Drain {
+    /// whether the pool distributions agree with the size within a factor of 2, the candidates
+    /// entering between them blur the measure.
+    pub fn agrees(&self) -> bool {
+        self.measured > 0.0 && (self.measured / self.expected).ln().abs() <= LN_2
+    }
+}
+
+/// The candidates of a pool distribution estimated eligible for a category.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Composition {
+    pub category: CategoryCode,
+    pub eligible: f64,
+    /// bounds of the estimate from the spread of the draws.
+    pub low: f64,
+    pub high: f64,
+    pub confidence: Confidence,
+    /// draws of the category the estimate is based on.
+    pub draws: usize,
+    /// pool changes around the draws checked against their size, and the ones agreeing.
+    pub drains: usize,
+    pub drains_agreeing: usize,
+}
+
+pub struct CompositionAnalyzer;
+
+impl CompositionAnalyzer {
+    /// the category draws alone between two consecutive pool distributions.
+    pub fn drains(
+        config: &AnalysisConfig,
+        pool_data: &[Pool],
+        invite_data: &[Invite],
+    ) -> Vec<Drain> {
+        pool_data
+            .windows(2)
+            .filter_map(|pools| {
+                let (before, after) = (pools[0], pools[1]);
+                let between = DateRange::new(Some(before.date), after.date.pred_opt());
+                let [invite] = between.invites(invite_data) else {
+                    return None;
+                };
+                let category = invite.category.code;
+                if category == CategoryCode::General || category == CategoryCode::Province {
+                    return None;
+                }
+
+                let above =
+                    |pool: Pool| ScorePool::from(pool).within_score(invite.score, config.pnp_score);
+                let total = above(before).total();
+                (total > 0.0).then(|| Drain {
+                    category,
+                    date: invite.date,
+                    measured: (total - above(after).total()) / total,
+                    expected: invite.size / total,
+                })
+            })
+            .collect()
+    }
+
+    /// the non-PNP candidates of each pool distribution eligible for each category, with the
+    /// eligibility fitted to the draws of the look-behind window before the distribution.
+    pub fn composition(
+        config: &AnalysisConfig,
+        pool_data: &[Pool],
+        invite_data: &[Invite],
+    ) -> Vec<(NaiveDate, Vec<Composition>)> {
+        let drains = Self::drains(config, pool_data, invite_data);
+
+        pool_data
+            .iter()
+            .map(|pool| {
+                let window = DateRange::new(
+                    Some(pool.date - Days::new(config.lookbehind_days)),
+                    Some(pool.date),
+                );
+                let model = EligibilityAnalyzer::fit(
+                    config,
+                    window.since_prior_pool(pool_data).pools(pool_data),
+                    window.invites(invite_data),
+                );
+                let candidates = ScorePool::from(*pool).non_pnp(config.pnp_score);
+
+                let estimates = model
+                    .categories
+                    .iter()
+                    .map(|eligibility| {
+                        let category = eligibility.category;
+                        let eligible = eligibility.eligible(candidates).total();
+                        // a single draw is known within a factor of 2
+                        let spread =
+                            f64::max(eligibility.error, LN_2 / (eligibility.draws as f64).sqrt());
+                        let (drains, drains_agreeing) = drains
+                            .iter()
+                            .filter(|drain| {
+                                drain.category == category && window.contains(drain.date)
+                            })
+                            .fold((0, 0), |(n, agreeing), drain| {
+                                (n + 1, agreeing + drain.agrees() as usize)
+                            });
+
+                        let mut confidence = if eligibility.draws >= 6 && spread < 0.2 {
+                            Confidence::High
+                        } else if eligibility.draws >= 3 && spread < 0.5 {
+                            Confidence::Medium
+                        } else {
+                            Confidence::Low
+                        };
+                        if drains_agreeing * 2 < drains {
+                            confidence = confidence.lower();
+                        }
+
+                        Composition {
+                            category,
+                            eligible,
+                            low: eligible * (-spread).exp(),
+                            high: f64::min(candidates.total(), eligible * spread.exp()),
+                            confidence,
+                            draws: eligibility.draws,
+                            drains,
+                            drains_agreeing,
+                        }
+                    })
+                    .collect();
+                (pool.date, estimates)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CompositionAnalyzer, Confidence};
+    use crate::analyze::config::AnalysisConfig;
+    use crate::data::test_util::{invites, pools};
+    use crate::data::CategoryCode;
+    use chrono::{Days, NaiveDate};
+
+    #[tokio::test]
+    async fn composition() {
+        let first = NaiveDate::from_ymd_opt(2024, 1, 10).unwrap();
+        let mut pool_data = pools(&[
+            (first, 1000.0),
+            (first + Days::new(14), 1000.0),
+            (first + Days::new(28), 1000.0),
+        ]);
+        // the pool above 500 loses the 500 candidates STEM invited between the two first
+        // distributions
+        pool_data[1].data[13] -= 500.0;
+        let stem = "STEM occupations (2024-1)";
+        let invite_data = invites(&[
+            (first + Days::new(1), stem, 500.0, 500.0),
+            (first + Days::new(15), stem, 2000.0, 450.0),
+        ]);
+        let config = AnalysisConfig::DEFAULT;
+
+        let drains = CompositionAnalyzer::drains(&config, &pool_data, &invite_data);
+        assert_eq!(drains.len(), 2);
+        assert_eq!(drains[0].measured, drains[0].expected);
+        assert!(drains[0].agrees());
+        // the pool came back, the candidates entering hide the draw
+        assert!(!drains[1].agrees());
+
+        let composition = CompositionAnalyzer::composition(&config, &pool_data, &invite_data);
+        assert_eq!(composition.len(), 3);
+        let (date, estimates) = &composition[1];
+        assert_eq!(*date, first + Days::new(14));
+        assert_eq!(estimates.len(), 1);
+        let stem = estimates[0];
+        assert_eq!(stem.category, CategoryCode::Stem);
+        assert_eq!(stem.draws, 1);
+        assert_eq!(stem.confidence, Confidence::Low);
+        assert!(stem.low < stem.eligible && stem.eligible < stem.high);
+        // the two draws disagree on the share
+        let stem = composition[2].1[0];
+        assert_eq!((stem.drains, stem.drains_agreeing), (2, 1));
+        assert!(stem.eligible > 0.0);
+    }
+}
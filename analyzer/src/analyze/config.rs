@@ -0,0 +1,101 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// Assumptions of the analysis, the defaults are the ones described in the README.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AnalysisConfig {
+    /// candidates who get their ITA submit their application uniformly within this window.
+    pub submit_days: u64,
+    /// window of the most recent observations used by projections.
+    pub lookbehind_days: u64,
+    /// factor of the exponential moving average and of the Kalman filter, per day.
+    pub smooth_alpha: f64,
+    /// window of the moving average, LOESS and Savitzky-Golay smoothing.
+    pub smooth_window_days: u64,
+    /// interval between two predicted draws.
+    pub predict_step_days: u64,
+    /// candidates above this score are considered as provincial nominees.
+    pub pnp_score: f64,
+}
+
+impl AnalysisConfig {
+    pub const DEFAULT: Self = Self {
+        submit_days: 15,
+        lookbehind_days: 365,
+        smooth_alpha: 0.03278688524, // 60 days window
+        smooth_window_days: 60,
+        predict_step_days: 14,
+        pnp_score: 600.0,
+    };
+
+    pub fn validate(self) -> Result<Self, String> {
+        if self.submit_days == 0 || self.submit_days > 365 {
+            return Err(format!(
+                "submit days {} is not within [1, 365]",
+                self.submit_days
+            ));
+        }
+        if self.lookbehind_days == 0 {
+            return Err("lookbehind days must be positive".into());
+        }
+        if !(self.smooth_alpha > 0.0 && self.smooth_alpha <= 1.0) {
+            return Err(format!(
+                "smooth alpha {} is not within (0, 1]",
+                self.smooth_alpha
+            ));
+        }
+        if self.smooth_window_days < 2 {
+            return Err("smooth window days must be at least 2".into());
+        }
+        if self.predict_step_days == 0 {
+            return Err("predict step days must be positive".into());
+        }
+        if !(self.pnp_score > 0.0 && self.pnp_score <= 1200.0) {
+            return Err(format!(
+                "PNP score {} is not within (0, 1200]",
+                self.pnp_score
+            ));
+        }
+        Ok(self)
+    }
+
+    pub fn current() -> Self {
+        *CURRENT.lock().unwrap()
+    }
+
+    pub fn set(config: Self) -> Result<(), String> {
+        *CURRENT.lock().unwrap() = config.validate()?;
+        Ok(())
+    }
+}
+
+impl Default for AnalysisConfig {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+static CURRENT: Mutex<AnalysisConfig> = Mutex::new(AnalysisConfig::DEFAULT);
+
+#[cfg(test)]
+mod tests {
+    use super::AnalysisConfig;
+
+    #[tokio::test]
+    async fn validate() {
+        assert!(AnalysisConfig::default().validate().is_ok());
+
+        let config = AnalysisConfig {
+            smooth_alpha: 0.0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = AnalysisConfig {
+            pnp_score: 1500.0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+}
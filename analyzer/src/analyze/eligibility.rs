@@ -0,0 +1,229 @@
+use super::calc::ScorePool;
+use super::config::AnalysisConfig;
+use crate::data::{CategoryCode, Invite, Pool};
+use itertools::Itertools;
+
+// the score the scale of the shares is taken at, and the score range of a unit of slope
+const SCORE_ORIGIN: f64 = 450.0;
+const SCORE_UNIT: f64 = 100.0;
+// slopes tried by the fit, per unit of score range
+const SLOPE_STEPS: i32 = 20;
+const SLOPE_STEP: f64 = 0.1;
+
+/// Share of the candidates of each score bucket eligible for a category, as
+/// `scale * exp(slope * (score - 450) / 100)` capped at 1, fitted to the sizes and cutoffs
+/// of its draws. The draws tell nothing below their lowest cutoff, the share is held there.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Eligibility {
+    pub category: CategoryCode,
+    /// share of the candidates at 450.
+    pub scale: f64,
+    /// positive when the eligible candidates score higher than the others.
+    pub slope: f64,
+    /// lowest cutoff of the draws.
+    pub floor: f64,
+    /// draws the fit is based on.
+    pub draws: usize,
+    /// root mean square of the relative errors of the fitted draw sizes, in log.
+    pub error: f64,
+}
+
+impl Eligibility {
+    fn mid_score(i: usize) -> f64 {
+        (ScorePool::min_score(i) + ScorePool::max_score(i)) as f64 / 2.0
+    }
+
+    // the growth of the shares by bucket, before the scale
+    fn weight(slope: f64, floor: f64) -> ScorePool {
+        let mut weight = ScorePool::zero();
+        for i in 0..Pool::N {
+            let score = f64::max(floor, Self::mid_score(i));
+            weight[i] = (slope * (score - SCORE_ORIGIN) / SCORE_UNIT).exp();
+        }
+        weight
+    }
+
+    /// the share of each score bucket.
+    pub fn share(&self) -> ScorePool {
+        let mut share = Self::weight(self.slope, self.floor) * self.scale;
+        for i in 0..Pool::N {
+            share[i] = f64::min(1.0, share[i]);
+        }
+        share
+    }
+
+    /// the candidates of the pool eligible for the category.
+    pub fn eligible(&self, pool: ScorePool) -> ScorePool {
+        pool * self.share()
+    }
+
+    // least squares of the log of the sizes, over the slopes from flat to the steepest so that
+    // a single draw keeps the shares flat
+    fn fit(category: CategoryCode, observations: &[(ScorePool, f64, f64)]) -> Option<Self> {
+        let observations: Vec<_> = observations
+            .iter()
+            .filter(|(above, size, _)| above.total() > 0.0 && *size > 0.0)
+            .collect();
+        let floor = observations
+            .iter()
+            .map(|(_, _, cutoff)| *cutoff)
+            .reduce(f64::min)?;
+
+        let n = observations.len() as f64;
+        let mut best: Option<Self> = None;
+        for step in (-SLOPE_STEPS..=SLOPE_STEPS).sorted_by_key(|step| (step.abs(), *step)) {
+            let slope = step as f64 * SLOPE_STEP;
+            let weight = Self::weight(slope, floor);
+            let residuals: Vec<_> = observations
+                .iter()
+                .map(|(above, size, _)| size.ln() - (*above * weight).total().ln())
+                .collect();
+            let log_scale = residuals.iter().sum::<f64>() / n;
+            let sse: f64 = residuals.iter().map(|x| (x - log_scale).powi(2)).sum();
+            let error = (sse / n).sqrt();
+            if best.is_none_or(|best| error < best.error - 1e-9) {
+                best = Some(Self {
+                    category,
+                    scale: log_scale.exp(),
+                    slope,
+                    floor,
+                    draws: observations.len(),
+                    error,
+                });
+            }
+        }
+        best
+    }
+}
+
+/// The eligibility of the categories with draws, the categories overlap as if being eligible
+/// for one of them did not change the chances for another at the same score.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EligibilityModel {
+    pub categories: Vec<Eligibility>,
+}
+
+impl EligibilityModel {
+    pub fn get(&self, category: CategoryCode) -> Option<&Eligibility> {
+        self.categories.iter().find(|x| x.category == category)
+    }
+
+    /// the candidates of the pool eligible for the category, all of them without estimate.
+    pub fn eligible(&self, category: CategoryCode, pool: ScorePool) -> ScorePool {
+        match self.get(category) {
+            Some(eligibility) => eligibility.eligible(pool),
+            None => pool,
+        }
+    }
+}
+
+pub struct EligibilityAnalyzer;
+
+impl EligibilityAnalyzer {
+    /// fits the eligibility of every category to its draws, with the candidates above each
+    /// cutoff taken from the pool in effect, less the ones invited since.
+    pub fn fit(
+        config: &AnalysisConfig,
+        pool_data: &[Pool],
+        invite_data: &[Invite],
+    ) -> EligibilityModel {
+        // the candidates above the cutoff, the size and the cutoff of each category draw
+        let mut observations: Vec<(CategoryCode, ScorePool, f64, f64)> = Vec::new();
+
+        let mut pools = pool_data.iter().peekable();
+        let mut pool_to_invite: Option<ScorePool> = None;
+        for invite in invite_data {
+            while let Some(pool) = pools.next_if(|pool| pool.date <= invite.date) {
+                pool_to_invite = Some(ScorePool::from(*pool));
+            }
+            let Some(pool) = pool_to_invite else {
+                continue;
+            };
+            pool_to_invite = Some(pool - pool.invite(invite, config.pnp_score));
+
+            let category = invite.category.code;
+            if category != CategoryCode::General
+                && category != CategoryCode::Province
+                && category != CategoryCode::Invalid
+            {
+                let above = pool.within_score(invite.score, config.pnp_score);
+                observations.push((category, above, invite.size, invite.score));
+            }
+        }
+
+        let categories = observations
+            .iter()
+            .map(|(category, _, _, _)| *category)
+            .unique()
+            .sorted_by_key(|category| *category as usize)
+            .filter_map(|category| {
+                let observations: Vec<_> = observations
+                    .iter()
+                    .filter(|(code, _, _, _)| *code == category)
+                    .map(|(_, above, size, cutoff)| (*above, *size, *cutoff))
+                    .collect();
+                Eligibility::fit(category, &observations)
+            })
+            .collect();
+
+        EligibilityModel { categories }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Eligibility, EligibilityAnalyzer};
+    use crate::analyze::calc::ScorePool;
+    use crate::analyze::config::AnalysisConfig;
+    use crate::data::test_util::{invites, pools};
+    use crate::data::CategoryCode;
+    use chrono::{Days, NaiveDate};
+
+    #[tokio::test]
+    async fn eligibility() {
+        let first = NaiveDate::from_ymd_opt(2024, 1, 10).unwrap();
+        // STEM candidates are 10% of the pool at 450 and score higher than the others
+        let truth = Eligibility {
+            category: CategoryCode::Stem,
+            scale: 0.1,
+            slope: 0.5,
+            floor: 470.0,
+            draws: 0,
+            error: 0.0,
+        };
+        let cutoffs = [470.0, 490.0, 520.0];
+
+        // a pool distribution before each draw
+        let dates: Vec<_> = (0..cutoffs.len())
+            .map(|i| (first + Days::new(14 * i as u64), 1000.0))
+            .collect();
+        let pool_data = pools(&dates);
+        let draws: Vec<_> = cutoffs
+            .iter()
+            .zip(&pool_data)
+            .map(|(score, pool)| {
+                let above = ScorePool::from(*pool).within_score(*score, 600.0);
+                let size = truth.eligible(above).total();
+                (pool.date, "STEM occupations (2024-1)", size, *score)
+            })
+            .collect();
+        let invite_data = invites(&draws);
+
+        let model = EligibilityAnalyzer::fit(&AnalysisConfig::DEFAULT, &pool_data, &invite_data);
+        let stem = model.get(CategoryCode::Stem).unwrap();
+        assert_eq!(stem.draws, 3);
+        assert!((stem.slope - 0.5).abs() < 1e-9);
+        assert!((stem.scale - 0.1).abs() < 1e-6);
+        assert!(stem.error < 1e-6);
+        assert!(model.get(CategoryCode::French).is_none());
+
+        // a single draw cannot tell the slope
+        let model =
+            EligibilityAnalyzer::fit(&AnalysisConfig::DEFAULT, &pool_data, &invite_data[..1]);
+        assert_eq!(model.get(CategoryCode::Stem).unwrap().slope, 0.0);
+
+        // no estimate without draws
+        let pool = ScorePool::from(pool_data[0]);
+        assert_eq!(model.eligible(CategoryCode::French, pool), pool);
+    }
+}
@@ -1,8 +1,15 @@
+pub(crate) mod anomaly;
 pub(crate) mod calc;
+pub(crate) mod config;
 pub(crate) mod rate;
 pub(crate) mod category;
+pub(crate) mod composition;
+pub(crate) mod eligibility;
 pub(crate) mod smooth;
 pub(crate) mod non_pnp;
+pub(crate) mod pace;
 pub(crate) mod predict;
-
-const LOOKBEHIND_DAYS: i64 = 365;
+pub(crate) mod program;
+pub(crate) mod provincial;
+pub(crate) mod regression;
+pub(crate) mod summary;
@@ -1,11 +1,37 @@
 use super::calc::ScorePool;
+use super::config::AnalysisConfig;
 use crate::data::{CategoryCode, Invite, Pool};
 
 pub struct PnpRemover;
 
 impl PnpRemover {
-    pub fn remove_pnp(pool_data: &[Pool], invite_data: &[Invite]) -> Vec<Invite> {
-        let mut non_pnp_invite = Vec::with_capacity(invite_data.len());
+    pub fn remove_pnp(
+        config: &AnalysisConfig,
+        pool_data: &[Pool],
+        invite_data: &[Invite],
+    ) -> Vec<Invite> {
+        invite_data
+            .iter()
+            .zip(Self::split_pnp(config, pool_data, invite_data))
+            .filter(|(invite, _)| invite.category.code != CategoryCode::Province)
+            .map(|(invite, split)| {
+                let mut new_invite = *invite;
+                if let Some((_, non_pnp)) = split {
+                    new_invite.size = non_pnp;
+                }
+                new_invite
+            })
+            .collect()
+    }
+
+    /// estimated (PNP, non-PNP) invited candidates of each invitation, `None` if
+    /// there is no pool data before the invitation.
+    pub fn split_pnp(
+        config: &AnalysisConfig,
+        pool_data: &[Pool],
+        invite_data: &[Invite],
+    ) -> Vec<Option<(f64, f64)>> {
+        let mut splits = Vec::with_capacity(invite_data.len());
 
         let mut pool_data: Vec<_> = pool_data.iter().copied().rev().collect();
         let mut pool_to_invite = None;
@@ -18,20 +44,17 @@ impl PnpRemover {
             }
 
             if let Some(pool) = pool_to_invite {
-                let invite_as_pool = pool.invite(invite);
+                let invite_as_pool = pool.invite(invite, config.pnp_score);
                 pool_to_invite = Some(pool - invite_as_pool);
 
-                if invite.category.code != CategoryCode::Province {
-                    let mut new_invite = *invite;
-                    new_invite.size = invite_as_pool.non_pnp().total();
-                    non_pnp_invite.push(new_invite);
-                }
+                splits.push(Some((
+                    invite_as_pool.pnp(config.pnp_score).total(),
+                    invite_as_pool.non_pnp(config.pnp_score).total(),
+                )));
             } else {
-                if invite.category.code != CategoryCode::Province {
-                    non_pnp_invite.push(*invite);
-                }
+                splits.push(None);
             }
         }
-        non_pnp_invite
+        splits
     }
 }
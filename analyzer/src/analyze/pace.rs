@@ -0,0 +1,240 @@
+use super::config::AnalysisConfig;
+use crate::data::{Invite, Plan};
+use chrono::{Datelike, Days, NaiveDate};
+
+/// How the planned invitations are spread over the year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaceShape {
+    /// evenly over the days.
+    Linear,
+    /// as the invitations of the previous years, on average.
+    Historical,
+}
+
+impl PaceShape {
+    pub fn parse(x: &str) -> Option<Self> {
+        match x {
+            "linear" => Some(Self::Linear),
+            "historical" => Some(Self::Historical),
+            _ => None,
+        }
+    }
+}
+
+/// Cumulative invitations of a year against the pace of its plan, one value per day of the year.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pace {
+    pub year: i32,
+    /// up to the last day of the data.
+    pub actual: Vec<Option<f64>>,
+    /// the pace of the minimum and of the maximum of the plan.
+    pub pace_min: Vec<f64>,
+    pub pace_max: Vec<f64>,
+    /// from the last day of the data to the end of the year, with the draws going on at their
+    /// recent cadence, `None` before.
+    pub projected: Vec<Option<f64>>,
+}
+
+impl Pace {
+    /// index of the last day of the data within the year.
+    pub fn last_day(&self) -> Option<usize> {
+        self.actual.iter().rposition(|x| x.is_some())
+    }
+
+    /// invitations ahead of (positive) or behind (negative) the middle of the plan pace,
+    /// on the last day of the data.
+    pub fn gap(&self) -> Option<f64> {
+        let day = self.last_day()?;
+        Some(self.actual[day]? - (self.pace_min[day] + self.pace_max[day]) / 2.0)
+    }
+}
+
+pub struct PaceAnalyzer;
+
+impl PaceAnalyzer {
+    fn first_day(year: i32) -> NaiveDate {
+        NaiveDate::from_yo_opt(year, 1).unwrap()
+    }
+
+    fn days(year: i32) -> usize {
+        (Self::first_day(year + 1) - Self::first_day(year)).num_days() as usize
+    }
+
+    /// cumulative invitations of the year at the end of each day, up to `last` (inclusive).
+    pub fn cumulative(invite_data: &[Invite], year: i32, last: NaiveDate) -> Vec<Option<f64>> {
+        let mut invites = invite_data
+            .iter()
+            .filter(|invite| invite.date.year() == year)
+            .peekable();
+        let mut total = 0.0;
+        (0..Self::days(year))
+            .map(|day| {
+                let date = Self::first_day(year) + Days::new(day as u64);
+                while let Some(invite) = invites.next_if(|invite| invite.date <= date) {
+                    total += invite.size;
+                }
+                Some(total).filter(|_| date <= last)
+            })
+            .collect()
+    }
+
+    // the share of the invitations of the year at the end of each day, on average over the years
+    fn historical_shape(invite_data: &[Invite], days: usize, past_years: &[i32]) -> Vec<f64> {
+        let shapes: Vec<Vec<f64>> = past_years
+            .iter()
+            .filter_map(|year| {
+                let last = Self::first_day(year + 1) - Days::new(1);
+                let cumulative: Vec<_> = Self::cumulative(invite_data, *year, last)
+                    .into_iter()
+                    .flatten()
+                    .collect();
+                let total = *cumulative.last()?;
+                (total > 0.0).then(|| cumulative.iter().map(|x| x / total).collect())
+            })
+            .collect();
+        if shapes.is_empty() {
+            return Self::linear_shape(days);
+        }
+
+        // the days of the leap years are matched by their index
+        (0..days)
+            .map(|day| {
+                shapes
+                    .iter()
+                    .map(|shape| shape[day.min(shape.len() - 1)])
+                    .sum::<f64>()
+                    / shapes.len() as f64
+            })
+            .collect()
+    }
+
+    fn linear_shape(days: usize) -> Vec<f64> {
+        (1..=days).map(|day| day as f64 / days as f64).collect()
+    }
+
+    // the draws within the look-behind window go on at their average gap and size
+    fn project(
+        config: &AnalysisConfig,
+        invite_data: &[Invite],
+        last: NaiveDate,
+        actual: &[Option<f64>],
+    ) -> Vec<Option<f64>> {
+        let days = actual.len();
+        let mut projected = vec![None; days];
+        let Some(last_day) = actual.iter().rposition(|x| x.is_some()) else {
+            return projected;
+        };
+        if last_day + 1 == days {
+            return projected;
+        }
+
+        let since = last - Days::new(config.lookbehind_days);
+        let mut draw_days: Vec<_> = invite_data
+            .iter()
+            .filter(|invite| since < invite.date && invite.date <= last)
+            .map(|invite| invite.date)
+            .collect();
+        draw_days.dedup();
+        let total: f64 = invite_data
+            .iter()
+            .filter(|invite| since < invite.date && invite.date <= last)
+            .map(|invite| invite.size)
+            .sum();
+        let mut value = actual[last_day].unwrap_or(0.0);
+        projected[last_day] = Some(value);
+        if draw_days.len() < 2 {
+            return projected;
+        }
+
+        let n = draw_days.len() as f64;
+        let gap = (draw_days[draw_days.len() - 1] - draw_days[0]).num_days() as f64 / (n - 1.0);
+        let size = total / n;
+        let mut next = last_day as f64 + gap;
+        for (day, x) in projected.iter_mut().enumerate().skip(last_day + 1) {
+            while next <= day as f64 {
+                value += size;
+                next += gap;
+            }
+            *x = Some(value);
+        }
+        projected
+    }
+
+    /// the pace of the plan of its year, with the invitations up to the end of the data.
+    /// the historical shape averages `past_years`, which should be over, the linear one is used
+    /// if there is none.
+    pub fn pace(
+        config: &AnalysisConfig,
+        invite_data: &[Invite],
+        plan: &Plan,
+        shape: PaceShape,
+        past_years: &[i32],
+    ) -> Pace {
+        let year = plan.year;
+        let days = Self::days(year);
+        let last = invite_data
+            .last()
+            .map(|invite| invite.date)
+            .unwrap_or(Self::first_day(year) - Days::new(1));
+        let actual = Self::cumulative(invite_data, year, last);
+
+        let shape = match shape {
+            PaceShape::Linear => Self::linear_shape(days),
+            PaceShape::Historical => Self::historical_shape(invite_data, days, past_years),
+        };
+        let projected = Self::project(config, invite_data, last, &actual);
+
+        Pace {
+            year,
+            actual,
+            pace_min: shape.iter().map(|x| x * plan.min).collect(),
+            pace_max: shape.iter().map(|x| x * plan.max).collect(),
+            projected,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PaceAnalyzer, PaceShape};
+    use crate::analyze::config::AnalysisConfig;
+    use crate::data::test_util::invites;
+    use crate::data::Plan;
+    use chrono::{Days, NaiveDate};
+
+    #[tokio::test]
+    async fn pace() {
+        // a draw of 1000 every 14 days since 2023-01-05, up to 2024-03-28
+        let first = NaiveDate::from_ymd_opt(2023, 1, 5).unwrap();
+        let draws: Vec<_> = (0..33)
+            .map(|i| (first + Days::new(14 * i), "General", 1000.0, 500.0))
+            .collect();
+        let invite_data = invites(&draws);
+        let config = AnalysisConfig::DEFAULT;
+        let plan = Plan::new(2024, 20000.0, 30000.0);
+
+        let pace = PaceAnalyzer::pace(&config, &invite_data, &plan, PaceShape::Linear, &[]);
+        assert_eq!(pace.actual.len(), 366);
+        // 2024-03-28, the 7th draw of the year
+        assert_eq!(pace.last_day(), Some(87));
+        assert_eq!(pace.actual[87], Some(7000.0));
+        assert_eq!(pace.actual[88], None);
+        assert_eq!(pace.pace_max[365], 30000.0);
+        let gap = pace.gap().unwrap();
+        assert!((gap - (7000.0 - 25000.0 * 88.0 / 366.0)).abs() < 1e-6);
+        // 19 more draws of 1000 every 14 days
+        assert_eq!(pace.projected[87], Some(7000.0));
+        assert_eq!(pace.projected[365], Some(26000.0));
+
+        // the first of the 26 draws of 2023 was on 01-05
+        let pace = PaceAnalyzer::pace(&config, &invite_data, &plan, PaceShape::Historical, &[2023]);
+        assert_eq!(pace.pace_min[3], 0.0);
+        assert!((pace.pace_min[4] - 20000.0 / 26.0).abs() < 1e-6);
+        assert_eq!(pace.pace_max[365], 30000.0);
+
+        let plan = Plan::new(2023, 20000.0, 30000.0);
+        let pace = PaceAnalyzer::pace(&config, &invite_data, &plan, PaceShape::Linear, &[]);
+        assert!(pace.projected.iter().all(|x| x.is_none()));
+        assert_eq!(pace.actual[364], Some(26000.0));
+    }
+}
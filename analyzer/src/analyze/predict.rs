@@ -1,4 +1,4 @@
-use crate::{data::{CategoryCode, Invite, Plan, Pool}, utils::console_log};
+use crate::data::{CategoryCode, Invite, Plan, Pool, Program};
 use chrono::{Datelike, Days, NaiveDate, Weekday};
 use itertools::Itertools;
 use std::collections::HashSet;
@@ -6,79 +6,127 @@ use std::collections::HashSet;
 use super::{
     calc::{CategoryPool, ScorePool},
     category::CategoryAnalyzer,
+    config::AnalysisConfig,
+    eligibility::{Eligibility, EligibilityAnalyzer, EligibilityModel},
     non_pnp::PnpRemover,
+    program::ProgramAnalyzer,
     rate::RateAnalyzer,
-    LOOKBEHIND_DAYS,
 };
 
+// the programs projected even without recent draws
+const PROJECTED_PROGRAMS: [Program; 2] = [Program::Cec, Program::Fsw];
+
 pub struct Predictor;
 
 impl Predictor {
     fn enter_rate(
-        pool_data: &[Pool],
-        invite_data: &[Invite],
-        start: NaiveDate,
-        end: NaiveDate,
+        config: &AnalysisConfig,
+        rate_label: &[NaiveDate],
+        rate_data: &[ScorePool],
     ) -> ScorePool {
-        let (label, rates) = RateAnalyzer::pool_increase_rate(pool_data, invite_data);
-        let rate = RateAnalyzer::projected_rate(&label, &rates);
-        ScorePool::max(rate.non_pnp(), ScorePool::zero()) * (end - start).num_days() as f64
+        let rate = RateAnalyzer::projected_rate(config, rate_label, rate_data);
+        ScorePool::max(rate.non_pnp(config.pnp_score), ScorePool::zero())
     }
 
     fn category_date(
+        config: &AnalysisConfig,
         pool_data: &[Pool],
         invite_data: &[Invite],
-    ) -> (CategoryPool, CategoryPool, HashSet<CategoryCode>) {
+    ) -> (CategoryPool, EligibilityModel, HashSet<CategoryCode>) {
         let last_date = invite_data.last().map(|invite| invite.date).unwrap();
 
         let invites_for_category: Vec<_> = invite_data
             .iter()
             .copied()
-            .filter(|invite| (last_date - invite.date).num_days() < LOOKBEHIND_DAYS)
+            .filter(|invite| (last_date - invite.date).num_days() < config.lookbehind_days as i64)
             .collect();
         let (_, count, c0) =
-            CategoryAnalyzer::invite_per_category(pool_data, &invites_for_category);
+            CategoryAnalyzer::invite_per_category(config, pool_data, &invites_for_category);
         let invite_pct = count.last().unwrap().non_pnp().normalize();
 
-        let (_, pct, c1) = CategoryAnalyzer::percent_per_category(pool_data, &invites_for_category);
-        let pool_pct = pct
-            .into_iter()
-            .map(|pool| pool.non_pnp())
-            .reduce(|x, y| x.max(y))
-            .unwrap();
+        // the eligible candidates of each category and their score distribution
+        let pool_pct = EligibilityAnalyzer::fit(config, pool_data, &invites_for_category);
+        let c1: HashSet<_> = pool_pct
+            .categories
+            .iter()
+            .map(|eligibility| eligibility.category)
+            .collect();
 
         let mut categories: HashSet<_> = c0.union(&c1).copied().collect();
         categories.remove(&CategoryCode::Province);
         (invite_pct, pool_pct, categories)
     }
 
-    fn final_pool(pool_data: &[Pool], invite_data: &[Invite]) -> ScorePool {
+    /// the programs without recent draws, projected as a draw of their usual size among the
+    /// eligible candidates fitted over the whole history.
+    fn program_draws(
+        config: &AnalysisConfig,
+        pool_data: &[Pool],
+        invite_data: &[Invite],
+        categories: &HashSet<CategoryCode>,
+    ) -> Vec<(CategoryCode, Eligibility, f64)> {
+        let projected: Vec<_> = PROJECTED_PROGRAMS
+            .iter()
+            .filter_map(|program| Some((program.category()?, *program)))
+            .filter(|(category, _)| !categories.contains(category))
+            .collect();
+        if projected.is_empty() {
+            return Vec::new();
+        }
+
+        let model = EligibilityAnalyzer::fit(config, pool_data, invite_data);
+        projected
+            .into_iter()
+            .filter_map(|(category, program)| {
+                let eligibility = model.get(category)?;
+                let size = ProgramAnalyzer::usual_size(invite_data, program)?;
+                Some((category, *eligibility, size))
+            })
+            .collect()
+    }
+
+    /// the categories in the order they invite in turn, the one waiting the longest since its
+    /// last draw first, as the category rounds rotate.
+    fn draw_order(invite_data: &[Invite], categories: &HashSet<CategoryCode>) -> Vec<CategoryCode> {
+        categories
+            .iter()
+            .copied()
+            .sorted_by_key(|category| {
+                let last_draw = invite_data
+                    .iter()
+                    .rev()
+                    .find(|invite| invite.category.code == *category)
+                    .map(|invite| invite.date);
+                (last_draw, *category as usize)
+            })
+            .collect()
+    }
+
+    fn final_pool(
+        config: &AnalysisConfig,
+        pool_data: &[Pool],
+        invite_data: &[Invite],
+    ) -> ScorePool {
         let pool_date = pool_data.last().unwrap().date;
         let mut pool = ScorePool::from(*pool_data.last().unwrap());
         for invite in invite_data {
             if invite.date > pool_date {
-                let invite_as_pool = pool.invite(invite);
+                let invite_as_pool = pool.invite(invite, config.pnp_score);
                 pool = pool - invite_as_pool;
             }
         }
-        pool.non_pnp()
+        pool.non_pnp(config.pnp_score)
     }
 
-    fn leave_rate(
-        pool_data: &[Pool],
-        invite_data: &[Invite],
-        plan_data: &[Plan],
-        start: NaiveDate,
-        end: NaiveDate,
-    ) -> f64 {
-        let invite_data = PnpRemover::remove_pnp(pool_data, invite_data);
-
-        let invited = invite_data
+    fn invited(non_pnp_invite: &[Invite], year: i32) -> f64 {
+        non_pnp_invite
             .iter()
-            .filter(|invite| invite.date.year() == start.year())
+            .filter(|invite| invite.date.year() == year)
             .map(|invite| invite.size)
-            .sum::<f64>();
+            .sum::<f64>()
+    }
 
+    fn leave_rate(plan_data: &[Plan], invited: f64, start: NaiveDate, end: NaiveDate) -> f64 {
         let middle_years = plan_data
             .iter()
             .filter(|plan| start.year() <= plan.year && plan.year < end.year())
@@ -94,52 +142,132 @@ impl Predictor {
             * end.ordinal0() as f64
             / (365 + end.leap_year() as u32) as f64;
 
-        return f64::max(0.0, middle_years + last_year - invited);
+        f64::max(0.0, middle_years + last_year - invited)
     }
 
     pub fn predict(
+        config: &AnalysisConfig,
         pool_data: &[Pool],
         invite_data: &[Invite],
         plan_data: &[Plan],
     ) -> (Vec<NaiveDate>, Vec<CategoryPool>, HashSet<CategoryCode>) {
         let last_invite_date = invite_data.last().map(|invite| invite.date).unwrap();
 
-        let (invite_pct, pool_pct, categories) = Self::category_date(pool_data, invite_data);
-        let final_pool = Self::final_pool(pool_data, invite_data);
-        let mut i = last_invite_date.week(Weekday::Sun).first_day() + Days::new(3) + Days::new(14);
+        let (invite_pct, pool_pct, mut categories) =
+            Self::category_date(config, pool_data, invite_data);
+        let program_draws = Self::program_draws(config, pool_data, invite_data, &categories);
+        let final_pool = Self::final_pool(config, pool_data, invite_data);
+        let draw_order = Self::draw_order(invite_data, &categories);
+
+        // everything below only depends on the history, compute it once for all steps
+        let (rate_label, rate_data) =
+            RateAnalyzer::pool_increase_rate(config, pool_data, invite_data);
+        let enter_rate = Self::enter_rate(config, &rate_label, &rate_data);
+        let non_pnp_invite = PnpRemover::remove_pnp(config, pool_data, invite_data);
+        let invited = Self::invited(&non_pnp_invite, last_invite_date.year());
+
+        let step = Days::new(config.predict_step_days);
+        let mut i = last_invite_date.week(Weekday::Sun).first_day() + Days::new(3) + step;
 
         let mut labels = Vec::new();
         let mut values = Vec::new();
 
-        while i < last_invite_date + Days::new(LOOKBEHIND_DAYS as u64) {
-            let enter_rate = Self::enter_rate(pool_data, invite_data, last_invite_date, i);
-            let leave_rate =
-                Self::leave_rate(pool_data, invite_data, plan_data, last_invite_date, i);
+        while i < last_invite_date + Days::new(config.lookbehind_days) {
+            let entered = enter_rate * (i - last_invite_date).num_days() as f64;
+            let leave_rate = Self::leave_rate(plan_data, invited, last_invite_date, i);
 
-            let pool_at_i = final_pool + enter_rate;
-            assert!(pool_at_i.pnp().total() == 0.0);
+            let pool_at_i = final_pool + entered;
+
+            // the general draws take the best candidates, then each category in turn invites
+            // among the eligible ones left, which also takes the ones eligible for the next
+            // categories
+            let pre_invite = leave_rate * invite_pct[CategoryCode::General];
+            let mut pool_left = pool_at_i.without_best(pre_invite);
 
             let mut item = CategoryPool::zero();
-            for category in &categories {
+            for category in &draw_order {
                 if *category == CategoryCode::General {
                     let plan_to_invite = leave_rate * invite_pct[*category];
                     let score = pool_at_i.score_of_invite(plan_to_invite);
                     item[*category] = score;
                 } else {
-                    let pre_invite = leave_rate * invite_pct[CategoryCode::General];
                     let plan_to_invite = leave_rate * invite_pct[*category];
-                    let adjustment = plan_to_invite / pool_pct[*category] + pre_invite;
-                    console_log!("{:?}: {}, {}", category, adjustment, pool_at_i.total());
-                    let score = pool_at_i.score_of_invite(adjustment);
+                    let eligible = pool_pct.eligible(*category, pool_left);
+                    let score = eligible.score_of_invite(plan_to_invite);
+                    pool_left = pool_left - eligible.within_score(score, 1200.0);
                     item[*category] = score;
                 }
             }
+            // what a draw of the program would take, without taking it from the next steps
+            for (category, eligibility, size) in &program_draws {
+                let eligible = eligibility.eligible(pool_left);
+                item[*category] = eligible.score_of_invite(*size);
+            }
             labels.push(i);
             values.push(item);
 
-            i = i + Days::new(14);
+            i = i + step;
         }
 
+        categories.extend(program_draws.iter().map(|(category, _, _)| *category));
         (labels, values, categories)
     }
+
+    /// scores of the first `steps` predicted draws, sorted by date then by category.
+    pub fn upcoming(
+        config: &AnalysisConfig,
+        pool_data: &[Pool],
+        invite_data: &[Invite],
+        plan_data: &[Plan],
+        steps: usize,
+    ) -> Vec<(NaiveDate, CategoryCode, f64)> {
+        if pool_data.is_empty() || invite_data.is_empty() {
+            return Vec::new();
+        }
+
+        let (labels, values, categories) = Self::predict(config, pool_data, invite_data, plan_data);
+        let categories: Vec<_> = categories
+            .into_iter()
+            .sorted_by_key(|category| *category as usize)
+            .collect();
+        labels
+            .into_iter()
+            .zip(values)
+            .take(steps)
+            .flat_map(|(date, pool)| {
+                categories
+                    .iter()
+                    .map(move |category| (date, *category, pool[*category].round()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Predictor;
+    use crate::data::test_util::invites;
+    use crate::data::CategoryCode::{French, Health, Stem};
+    use chrono::NaiveDate;
+    use std::collections::HashSet;
+
+    #[tokio::test]
+    async fn draw_order() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+        let (stem, french) = (
+            "STEM occupations (2024-1)",
+            "French language proficiency (2024-1)",
+        );
+        let invite_data = invites(&[
+            (date(1, 10), stem, 500.0, 490.0),
+            (date(1, 24), french, 1000.0, 400.0),
+            (date(2, 7), "Healthcare occupations (2024-1)", 2000.0, 430.0),
+            (date(2, 21), stem, 500.0, 480.0),
+        ]);
+        let categories: HashSet<_> = [Stem, Health, French].into_iter().collect();
+
+        // French has waited the longest, STEM drew last
+        let order = Predictor::draw_order(&invite_data, &categories);
+        assert_eq!(order, vec![French, Health, Stem]);
+    }
 }
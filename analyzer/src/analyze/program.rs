@@ -0,0 +1,80 @@
+use crate::data::{Invite, Program};
+
+// the draws the usual size of a program is taken over
+const USUAL_DRAWS: usize = 3;
+
+pub struct ProgramAnalyzer;
+
+impl ProgramAnalyzer {
+    /// the draws of each program, with the programs without draw left out.
+    pub fn invite_per_program(invite_data: &[Invite]) -> Vec<(Program, Vec<&Invite>)> {
+        Program::values()
+            .iter()
+            .map(|program| {
+                let invites: Vec<_> = invite_data
+                    .iter()
+                    .filter(|invite| invite.program() == *program)
+                    .collect();
+                (*program, invites)
+            })
+            .filter(|(_, invites)| !invites.is_empty())
+            .collect()
+    }
+
+    /// the mean size of the last draws of the program.
+    pub fn usual_size(invite_data: &[Invite], program: Program) -> Option<f64> {
+        let sizes: Vec<_> = invite_data
+            .iter()
+            .rev()
+            .filter(|invite| invite.program() == program)
+            .take(USUAL_DRAWS)
+            .map(|invite| invite.size)
+            .collect();
+        (!sizes.is_empty()).then(|| sizes.iter().sum::<f64>() / sizes.len() as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProgramAnalyzer;
+    use crate::data::test_util::invites;
+    use crate::data::Program;
+    use chrono::{Days, NaiveDate};
+
+    #[tokio::test]
+    async fn invite_per_program() {
+        let first = NaiveDate::from_ymd_opt(2023, 1, 10).unwrap();
+        // the draws named after a program are restricted to it
+        let draws: Vec<_> = [
+            ("General", 5000.0),
+            ("Canadian Experience Class", 1000.0),
+            ("Provincial Nominee Program", 800.0),
+            ("Canadian Experience Class", 2000.0),
+            ("Canadian Experience Class", 3000.0),
+            ("Canadian Experience Class", 4000.0),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, (name, size))| (first + Days::new(7 * i as u64), *name, *size, 500.0))
+        .collect();
+        let invite_data = invites(&draws);
+
+        let programs = ProgramAnalyzer::invite_per_program(&invite_data);
+        let programs: Vec<_> = programs
+            .iter()
+            .map(|(program, invites)| (*program, invites.len()))
+            .collect();
+        assert_eq!(
+            programs,
+            vec![(Program::Mixed, 1), (Program::Cec, 4), (Program::Pnp, 1)]
+        );
+        assert_eq!(
+            ProgramAnalyzer::usual_size(&invite_data, Program::Cec),
+            Some(3000.0)
+        );
+        assert_eq!(
+            ProgramAnalyzer::usual_size(&invite_data, Program::Fsw),
+            None
+        );
+    }
+}
@@ -0,0 +1,179 @@
+use super::config::AnalysisConfig;
+use super::rate::RateAnalyzer;
+use crate::data::{Invite, Pool, Province, ProvincialDraw};
+use chrono::{Days, NaiveDate, Weekday};
+
+// the longest delay from a provincial invitation to the nomination looked for, in weeks
+const MAX_LAG_WEEKS: usize = 26;
+// the fewest weeks a correlation is computed on
+const MIN_WEEKS: usize = 8;
+
+/// The provincial invitations of a week, from Monday, and the candidates entering the PNP
+/// buckets of the pool within it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProvincialWeek {
+    pub start: NaiveDate,
+    /// indexed as `Province::values()`.
+    pub invited: Vec<f64>,
+    /// none outside the measured rates.
+    pub inflow: Option<f64>,
+}
+
+impl ProvincialWeek {
+    pub fn total(&self) -> f64 {
+        self.invited.iter().sum()
+    }
+}
+
+pub struct ProvincialAnalyzer;
+
+impl ProvincialAnalyzer {
+    /// the weeks from the first provincial draw or measured rate to the last one.
+    pub fn weekly(
+        config: &AnalysisConfig,
+        pool_data: &[Pool],
+        invite_data: &[Invite],
+        provincial_data: &[ProvincialDraw],
+    ) -> Vec<ProvincialWeek> {
+        let week = |date: NaiveDate| date.week(Weekday::Mon).first_day();
+        let dates = provincial_data
+            .iter()
+            .map(|draw| draw.date)
+            .chain(pool_data.iter().map(|pool| pool.date));
+        let (Some(first), Some(last)) = (dates.clone().min(), dates.max()) else {
+            return Vec::new();
+        };
+
+        let starts: Vec<_> = (0..)
+            .map(|i| week(first) + Days::new(7 * i))
+            .take_while(|start| *start <= last)
+            .collect();
+        let periods: Vec<_> = starts
+            .iter()
+            .map(|start| (*start, *start + Days::new(7)))
+            .collect();
+        let increases = RateAnalyzer::increase_within(config, pool_data, invite_data, &periods);
+
+        starts
+            .iter()
+            .zip(increases)
+            .map(|(start, increase)| {
+                let invited = Province::values()
+                    .iter()
+                    .map(|province| {
+                        provincial_data
+                            .iter()
+                            .filter(|draw| draw.province == *province && week(draw.date) == *start)
+                            .map(|draw| draw.size)
+                            .sum()
+                    })
+                    .collect();
+
+                ProvincialWeek {
+                    start: *start,
+                    invited,
+                    inflow: increase.map(|x| x.pnp(config.pnp_score).total()),
+                }
+            })
+            .collect()
+    }
+
+    /// the delay in weeks the inflow follows the provincial invitations the closest with, the
+    /// shortest of the equally close ones, and their correlation, over the weeks with an inflow.
+    /// None when the inflow does not follow them at any delay.
+    pub fn lag(weeks: &[ProvincialWeek]) -> Option<(usize, f64)> {
+        (0..=MAX_LAG_WEEKS)
+            .filter_map(|lag| {
+                let (invited, inflow): (Vec<_>, Vec<_>) = weeks
+                    .iter()
+                    .zip(weeks.iter().skip(lag))
+                    .filter_map(|(week, later)| Some((week.total(), later.inflow?)))
+                    .unzip();
+                if invited.len() < MIN_WEEKS {
+                    return None;
+                }
+                correlation(&invited, &inflow).map(|r| (lag, r))
+            })
+            .reduce(|best, x| if x.1 > best.1 + 1e-9 { x } else { best })
+            .filter(|(_, r)| *r > 0.0)
+    }
+}
+
+fn correlation(x: &[f64], y: &[f64]) -> Option<f64> {
+    let n = x.len() as f64;
+    let (mean_x, mean_y) = (x.iter().sum::<f64>() / n, y.iter().sum::<f64>() / n);
+    let (mut sxy, mut sxx, mut syy) = (0.0, 0.0, 0.0);
+    for (x, y) in x.iter().zip(y) {
+        sxy += (x - mean_x) * (y - mean_y);
+        sxx += (x - mean_x).powi(2);
+        syy += (y - mean_y).powi(2);
+    }
+    (sxx > 0.0 && syy > 0.0).then(|| sxy / (sxx * syy).sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ProvincialAnalyzer, ProvincialWeek};
+    use crate::analyze::config::AnalysisConfig;
+    use crate::data::{Pool, Province, ProvincialDraw};
+    use chrono::{Days, NaiveDate};
+
+    #[tokio::test]
+    async fn weekly() {
+        // Mondays
+        let first = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let config = AnalysisConfig::DEFAULT;
+        // the PNP bucket grows by 70 a week, the pool distributions are weekly
+        let pool_data: Vec<_> = (0..20)
+            .map(|i| {
+                let mut data = [1000.0; Pool::N];
+                data[Pool::N - 1] += 70.0 * i as f64;
+                Pool {
+                    data,
+                    date: first + Days::new(7 * i),
+                }
+            })
+            .collect();
+        let provincial_data = vec![
+            ProvincialDraw {
+                province: Province::Ontario,
+                date: first + Days::new(16),
+                size: 1000.0,
+                min_score: Some(470.0),
+                occupations: Vec::new(),
+            },
+            ProvincialDraw {
+                province: Province::BritishColumbia,
+                date: first + Days::new(18),
+                size: 200.0,
+                min_score: None,
+                occupations: Vec::new(),
+            },
+        ];
+
+        let weeks = ProvincialAnalyzer::weekly(&config, &pool_data, &[], &provincial_data);
+        assert_eq!(weeks.len(), 20);
+        assert_eq!(weeks[2].start, first + Days::new(14));
+        assert_eq!(weeks[2].total(), 1200.0);
+        assert_eq!(weeks[2].invited[Province::Ontario as usize], 1000.0);
+        // the rates of the first submit window are not measured
+        assert_eq!(weeks[0].inflow, None);
+        let inflow = weeks[15].inflow.unwrap();
+        assert!((inflow - 70.0).abs() < 1e-9, "{}", inflow);
+        assert_eq!(weeks[19].inflow, None);
+
+        // half the invitations enter the pool 3 weeks later
+        let invited = |i: usize| 100.0 * ((i * 7) % 11) as f64;
+        let weeks: Vec<_> = (0..30)
+            .map(|i| ProvincialWeek {
+                start: first + Days::new(7 * i as u64),
+                invited: vec![invited(i)],
+                inflow: (i >= 3).then(|| invited(i - 3) / 2.0),
+            })
+            .collect();
+        let (lag, r) = ProvincialAnalyzer::lag(&weeks).unwrap();
+        assert_eq!(lag, 3);
+        assert!((r - 1.0).abs() < 1e-9);
+        assert_eq!(ProvincialAnalyzer::lag(&weeks[..5]), None);
+    }
+}
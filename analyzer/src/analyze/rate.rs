@@ -2,7 +2,7 @@ use std::{cmp::Ordering, collections::BinaryHeap};
 
 use chrono::{Days, NaiveDate};
 
-use super::{calc::ScorePool, LOOKBEHIND_DAYS};
+use super::{calc::ScorePool, config::AnalysisConfig};
 use crate::data::{Invite, Pool};
 
 #[derive(Debug, Clone, Copy)]
@@ -68,23 +68,70 @@ impl RateAccumulator {
 pub struct RateAnalyzer;
 
 impl RateAnalyzer {
-    pub const SUBMIT_DAYS: usize = 15;
+    /// days of history before the first rate, the rates of the first submit window are
+    /// under estimated since the earlier invitations are missing.
+    pub fn warm_up_days(config: &AnalysisConfig) -> u64 {
+        config.submit_days
+    }
 
     pub fn pool_increase_rate(
+        config: &AnalysisConfig,
         pool_data: &[Pool],
         invite_data: &[Invite],
     ) -> (Vec<NaiveDate>, Vec<ScorePool>) {
+        Self::rate_segments(config, pool_data, invite_data)
+            .into_iter()
+            .map(|(start, end, rate)| {
+                let interval = (end - start).num_days();
+                (start + Days::new(((interval + 1) / 2) as u64), rate) // use the mid-point
+            })
+            .unzip()
+    }
+
+    /// the candidates entering the pool within each period, from its first day to the day before
+    /// its end, none for the periods the rates do not cover.
+    pub fn increase_within(
+        config: &AnalysisConfig,
+        pool_data: &[Pool],
+        invite_data: &[Invite],
+        periods: &[(NaiveDate, NaiveDate)],
+    ) -> Vec<Option<ScorePool>> {
+        let segments = Self::rate_segments(config, pool_data, invite_data);
+        let (Some(first), Some(last)) = (segments.first(), segments.last()) else {
+            return vec![None; periods.len()];
+        };
+
+        periods
+            .iter()
+            .map(|(start, end)| {
+                (first.0 <= *start && *end <= last.1).then(|| {
+                    segments
+                        .iter()
+                        .map(|(from, to, rate)| {
+                            let days = (*to.min(end) - *from.max(start)).num_days();
+                            *rate * days.max(0) as f64
+                        })
+                        .fold(ScorePool::zero(), |x, y| x + y)
+                })
+            })
+            .collect()
+    }
+
+    // the rate of each interval between the changes of rate, from its first day to the next
+    // interval
+    fn rate_segments(
+        config: &AnalysisConfig,
+        pool_data: &[Pool],
+        invite_data: &[Invite],
+    ) -> Vec<(NaiveDate, NaiveDate, ScorePool)> {
         if pool_data.len() == 0 {
-            return (Vec::new(), Vec::new());
+            return Vec::new();
         }
 
         let i_0 = pool_data.first().unwrap().date;
         let i_n = pool_data.last().unwrap().date + Days::new(1);
 
-        let (mut labels, mut rates) = {
-            let capacity = (i_n - i_0).num_days() as usize;
-            (Vec::with_capacity(capacity), Vec::with_capacity(capacity))
-        };
+        let mut segments = Vec::new();
 
         let mut pools: Vec<_> = pool_data.iter().copied().rev().collect();
         let mut invites: Vec<_> = invite_data
@@ -150,10 +197,10 @@ impl RateAnalyzer {
                     break;
                 }
 
-                let invite_as_pool = pool_to_invite.invite(invite);
+                let invite_as_pool = pool_to_invite.invite(invite, config.pnp_score);
                 rate_acc.insert(RateModifier {
-                    value: invite_as_pool / Self::SUBMIT_DAYS as f64,
-                    expiry: i + Days::new(Self::SUBMIT_DAYS as u64),
+                    value: invite_as_pool / config.submit_days as f64,
+                    expiry: i + Days::new(config.submit_days),
                 });
                 pool_to_invite = pool_to_invite - invite_as_pool; // remove already invited candidates from the pool to avoid duplicate counts.
 
@@ -168,26 +215,29 @@ impl RateAnalyzer {
                 }
             }
 
-            if i > i_0 + Days::new(Self::SUBMIT_DAYS as u64) {
+            if i > i_0 + Days::new(config.submit_days) {
                 // ignore first 60 days since they are under estimated.
-
-                let interval = (i_next - i).num_days();
-                labels.push(i + Days::new(((interval + 1) / 2) as u64)); // use the mid-point
-                rates.push(rate_acc.rate());
+                segments.push((i, i_next, rate_acc.rate()));
             }
 
             i = i_next;
         }
 
-        (labels, rates)
+        segments
     }
 
-    pub fn projected_rate(rate_label: &[NaiveDate], rate_data: &[ScorePool]) -> ScorePool {
+    pub fn projected_rate(
+        config: &AnalysisConfig,
+        rate_label: &[NaiveDate],
+        rate_data: &[ScorePool],
+    ) -> ScorePool {
         if let Some(last_date) = rate_label.last() {
             rate_data
                 .iter()
                 .zip(rate_label)
-                .filter(|(_, date)| (*last_date - **date).num_days() < LOOKBEHIND_DAYS)
+                .filter(|(_, date)| {
+                    (*last_date - **date).num_days() < config.lookbehind_days as i64
+                })
                 .map(|x| (*x.0, 1.0))
                 .reduce(|(x, n), (y, m)| (x + y, m + n))
                 .map(|(x, n)| x / n)
@@ -0,0 +1,260 @@
+use super::calc;
+use crate::data::{CategoryCode, DateRange, Invite, Pool};
+
+// the fewest draws of a category regressed
+const MIN_DRAWS: usize = 8;
+// the farthest a pool distribution is taken from a draw, in days
+const MAX_POOL_DAYS: i64 = 14;
+// the score above which the candidates of the pool are counted
+const POOL_SCORE: i64 = 500;
+
+/// A variable the cutoffs are regressed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CutoffVariable {
+    Size = 0,
+    /// days since the previous draw of the category.
+    Gap,
+    /// candidates above 500 in the pool distribution nearest to the draw.
+    Pool,
+}
+
+impl CutoffVariable {
+    pub const N: usize = 3;
+
+    pub fn values() -> &'static [CutoffVariable] {
+        &[Self::Size, Self::Gap, Self::Pool]
+    }
+
+    /// "size", "gap" or "pool".
+    pub fn parse(x: &str) -> Option<Self> {
+        Self::values()
+            .iter()
+            .find(|variable| variable.as_str() == x)
+            .copied()
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Size => "size",
+            Self::Gap => "gap",
+            Self::Pool => "pool",
+        }
+    }
+}
+
+/// A draw regressed, with its variables indexed as `CutoffVariable::values()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CutoffSample<'a> {
+    pub invite: &'a Invite,
+    pub x: [f64; CutoffVariable::N],
+    /// the cutoff less the fitted one.
+    pub residual: f64,
+}
+
+/// Least squares fit of the cutoffs of the draws of a category to their variables.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CutoffRegression<'a> {
+    pub category: CategoryCode,
+    pub intercept: f64,
+    /// change of the cutoff by unit of each variable, the others held.
+    pub coefficients: [f64; CutoffVariable::N],
+    /// share of the variance of the cutoffs the fit explains.
+    pub r_squared: f64,
+    pub samples: Vec<CutoffSample<'a>>,
+}
+
+impl CutoffRegression<'_> {
+    pub fn fitted(&self, x: &[f64; CutoffVariable::N]) -> f64 {
+        self.intercept
+            + self
+                .coefficients
+                .iter()
+                .zip(x)
+                .map(|(b, x)| b * x)
+                .sum::<f64>()
+    }
+
+    /// the mean of each variable over the draws.
+    pub fn mean(&self) -> [f64; CutoffVariable::N] {
+        let mut mean = [0.0; CutoffVariable::N];
+        for sample in &self.samples {
+            for (m, x) in mean.iter_mut().zip(sample.x) {
+                *m += x / self.samples.len() as f64;
+            }
+        }
+        mean
+    }
+}
+
+pub struct RegressionAnalyzer;
+
+impl RegressionAnalyzer {
+    /// the candidates above 500 of the pool distribution nearest to the date, if close enough.
+    fn pool_above(pool_data: &[Pool], invite: &Invite) -> Option<f64> {
+        let pool = pool_data
+            .iter()
+            .min_by_key(|pool| (pool.date - invite.date).num_days().abs())
+            .filter(|pool| (pool.date - invite.date).num_days().abs() <= MAX_POOL_DAYS)?;
+        Some(
+            (0..Pool::N)
+                .filter(|i| Pool::min_score(*i) >= POOL_SCORE)
+                .map(|i| pool.count(i))
+                .sum(),
+        )
+    }
+
+    /// the regression of each category with enough draws within the range, the earlier draws
+    /// only give the days since the previous draw. The first draw of a category and the draws
+    /// without a pool distribution within 14 days are left out, as are the categories whose
+    /// variables are collinear.
+    pub fn cutoff<'a>(
+        pool_data: &[Pool],
+        invite_data: &'a [Invite],
+        range: DateRange,
+    ) -> Vec<CutoffRegression<'a>> {
+        CategoryCode::values()
+            .iter()
+            .filter_map(|category| {
+                let invites: Vec<_> = invite_data
+                    .iter()
+                    .filter(|invite| invite.category.code == *category)
+                    .collect();
+                let samples: Vec<_> = invites
+                    .windows(2)
+                    .filter(|pair| range.contains(pair[1].date))
+                    .filter_map(|pair| {
+                        let (previous, invite) = (pair[0], pair[1]);
+                        let gap = (invite.date - previous.date).num_days() as f64;
+                        let pool = Self::pool_above(pool_data, invite)?;
+                        Some(CutoffSample {
+                            invite,
+                            x: [invite.size, gap, pool],
+                            residual: 0.0,
+                        })
+                    })
+                    .collect();
+                Self::fit(*category, samples)
+            })
+            .collect()
+    }
+
+    // centered so that the intercept does not weigh on the normal equations
+    fn fit(category: CategoryCode, mut samples: Vec<CutoffSample>) -> Option<CutoffRegression> {
+        if samples.len() < MIN_DRAWS {
+            return None;
+        }
+        let n = samples.len() as f64;
+        let mean_y = samples.iter().map(|x| x.invite.score).sum::<f64>() / n;
+        let mut mean_x = [0.0; CutoffVariable::N];
+        for sample in &samples {
+            for (m, x) in mean_x.iter_mut().zip(sample.x) {
+                *m += x / n;
+            }
+        }
+
+        // (X^T X | X^T y) of the centered variables
+        let mut a = vec![vec![0.0; CutoffVariable::N + 1]; CutoffVariable::N];
+        for sample in &samples {
+            let x: Vec<_> = sample.x.iter().zip(mean_x).map(|(x, m)| x - m).collect();
+            let y = sample.invite.score - mean_y;
+            for (r, row) in a.iter_mut().enumerate() {
+                for (c, value) in row.iter_mut().take(CutoffVariable::N).enumerate() {
+                    *value += x[r] * x[c];
+                }
+                row[CutoffVariable::N] += x[r] * y;
+            }
+        }
+        let b = calc::solve(a)?;
+        let mut coefficients = [0.0; CutoffVariable::N];
+        coefficients.copy_from_slice(&b);
+
+        let mut regression = CutoffRegression {
+            category,
+            intercept: mean_y
+                - coefficients
+                    .iter()
+                    .zip(mean_x)
+                    .map(|(b, m)| b * m)
+                    .sum::<f64>(),
+            coefficients,
+            r_squared: 0.0,
+            samples: Vec::new(),
+        };
+        for sample in samples.iter_mut() {
+            sample.residual = sample.invite.score - regression.fitted(&sample.x);
+        }
+        let ss_res: f64 = samples.iter().map(|x| x.residual.powi(2)).sum();
+        let ss_tot: f64 = samples
+            .iter()
+            .map(|x| (x.invite.score - mean_y).powi(2))
+            .sum();
+        regression.r_squared = if ss_tot > 0.0 {
+            1.0 - ss_res / ss_tot
+        } else {
+            1.0
+        };
+        regression.samples = samples;
+        Some(regression)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CutoffVariable, RegressionAnalyzer};
+    use crate::data::test_util::invites;
+    use crate::data::{CategoryCode, DateRange, Pool};
+    use chrono::{Days, NaiveDate};
+
+    #[tokio::test]
+    async fn cutoff() {
+        let first = NaiveDate::from_ymd_opt(2024, 1, 3).unwrap();
+        let (mut draws, mut pool_data) = (Vec::new(), Vec::new());
+        let mut date = first;
+        for i in 0..12 {
+            date = date + Days::new(7 + 7 * (i % 3));
+            let size = 1000.0 + 500.0 * (i % 4) as f64;
+            let gap = 7.0 + 7.0 * (i % 3) as f64;
+            let above = 10000.0 + 1000.0 * ((i * 3) % 5) as f64;
+            let mut data = [1000.0; Pool::N];
+            data[Pool::N - 2] = above / 2.0;
+            data[Pool::N - 1] = above / 2.0;
+            pool_data.push(Pool { data, date });
+            let score = 600.0 - 0.02 * size + 0.5 * gap - 0.003 * above;
+            draws.push((date, "General", size, score));
+        }
+        // too few STEM draws, and a General draw too far from any pool distribution
+        let stem = "STEM occupations (2024-1)";
+        for days in [1, 30] {
+            draws.push((date + Days::new(days), stem, 500.0, 490.0));
+        }
+        draws.push((date + Days::new(100), "General", 1000.0, 500.0));
+        let invite_data = invites(&draws);
+
+        let regressions = RegressionAnalyzer::cutoff(&pool_data, &invite_data, DateRange::ALL);
+        assert_eq!(regressions.len(), 1);
+        let regression = &regressions[0];
+        assert_eq!(regression.category, CategoryCode::General);
+        // the first draw has no previous one
+        assert_eq!(regression.samples.len(), 11);
+        for (b, expected) in regression.coefficients.iter().zip([-0.02, 0.5, -0.003]) {
+            assert!((b - expected).abs() < 1e-6, "{}", b);
+        }
+        assert!((regression.intercept - 600.0).abs() < 1e-3);
+        assert!((regression.r_squared - 1.0).abs() < 1e-9);
+        assert!(regression
+            .samples
+            .iter()
+            .all(|sample| sample.residual.abs() < 1e-6));
+        let gap = regression.samples[0].x[CutoffVariable::Gap as usize];
+        assert_eq!(gap, 14.0);
+
+        // the draws before the range still give the days since the previous draw
+        let range = DateRange::new(Some(invite_data[4].date), None);
+        let regressions = RegressionAnalyzer::cutoff(&pool_data, &invite_data, range);
+        assert_eq!(regressions[0].samples.len(), 8);
+        assert_eq!(regressions[0].samples[0].invite.id, invite_data[4].id);
+        let range = DateRange::new(Some(invite_data[5].date), None);
+        assert!(RegressionAnalyzer::cutoff(&pool_data, &invite_data, range).is_empty());
+        assert_eq!(CutoffVariable::parse("gap"), Some(CutoffVariable::Gap));
+    }
+}
@@ -1,7 +1,8 @@
 pub struct Smoother;
 use std::ops::{Add, Mul};
 use chrono::NaiveDate;
-use super::calc::ScorePool;
+use super::calc::{self, ScorePool};
+use super::config::AnalysisConfig;
 
 pub(crate) trait SmoothLabel: Copy {
     fn steps(self, other: Self) -> i64;
@@ -17,6 +18,46 @@ impl SmoothLabel for NaiveDate {
 
 impl SmoothValue for ScorePool {}
 
+impl SmoothValue for f64 {}
+
+// the longest history a smoothed value is computed from, in days
+const MAX_WARM_UP_DAYS: u64 = 3 * 365;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SmoothMethod {
+    None,
+    Exponential,
+    MovingAverage,
+    Loess,
+    SavitzkyGolay,
+    Kalman,
+}
+
+impl SmoothMethod {
+    pub fn parse(x: &str) -> Option<Self> {
+        match x {
+            "none" => Some(Self::None),
+            "ema" => Some(Self::Exponential),
+            "ma" => Some(Self::MovingAverage),
+            "loess" => Some(Self::Loess),
+            "sg" => Some(Self::SavitzkyGolay),
+            "kalman" => Some(Self::Kalman),
+            _ => None,
+        }
+    }
+
+    /// days of history the first smoothed value depends on, at most three years.
+    pub fn warm_up_days(self, config: &AnalysisConfig) -> u64 {
+        let days = match self {
+            Self::None => 0,
+            // the older values weigh less than 5%
+            Self::Exponential | Self::Kalman => (3.0 / config.smooth_alpha).ceil() as u64,
+            Self::MovingAverage | Self::Loess | Self::SavitzkyGolay => config.smooth_window_days / 2,
+        };
+        days.min(MAX_WARM_UP_DAYS)
+    }
+}
+
 impl Smoother {
     pub fn exponential<L, D>(labels: &[L], values: &mut [D], alpha: f64)
     where
@@ -42,4 +83,205 @@ impl Smoother {
             value_prev = tmp_value;
         }
     }
+
+    pub fn smooth<L, D>(config: &AnalysisConfig, method: SmoothMethod, labels: &[L], values: &mut [D])
+    where
+        L: SmoothLabel,
+        D: SmoothValue,
+    {
+        let window = config.smooth_window_days as f64;
+        match method {
+            SmoothMethod::None => (),
+            SmoothMethod::Exponential => Self::exponential(labels, values, config.smooth_alpha),
+            SmoothMethod::MovingAverage => Self::moving_average(labels, values, window),
+            SmoothMethod::Loess => Self::loess(labels, values, window),
+            SmoothMethod::SavitzkyGolay => Self::savitzky_golay(labels, values, window),
+            SmoothMethod::Kalman => Self::kalman(labels, values, config.smooth_alpha),
+        }
+    }
+
+    /// centered average of the values within the window.
+    pub fn moving_average<L, D>(labels: &[L], values: &mut [D], window: f64)
+    where
+        L: SmoothLabel,
+        D: SmoothValue,
+    {
+        Self::local_polynomial(labels, values, window / 2.0, 0, |_| 1.0);
+    }
+
+    /// locally weighted linear regression with tricube weights.
+    pub fn loess<L, D>(labels: &[L], values: &mut [D], window: f64)
+    where
+        L: SmoothLabel,
+        D: SmoothValue,
+    {
+        Self::local_polynomial(labels, values, window / 2.0, 1, |d| {
+            (1.0 - d.abs().powi(3)).powi(3)
+        });
+    }
+
+    /// least square fit of a quadratic within the window, the irregular interval
+    /// generalization of the Savitzky-Golay filter.
+    pub fn savitzky_golay<L, D>(labels: &[L], values: &mut [D], window: f64)
+    where
+        L: SmoothLabel,
+        D: SmoothValue,
+    {
+        Self::local_polynomial(labels, values, window / 2.0, 2, |_| 1.0);
+    }
+
+    /// local level model, the process noise grows with the interval between two
+    /// observations. `alpha` is the steady state gain of a daily observed series,
+    /// so the result is comparable to the exponential moving average of the same alpha.
+    pub fn kalman<L, D>(labels: &[L], values: &mut [D], alpha: f64)
+    where
+        L: SmoothLabel,
+        D: SmoothValue,
+    {
+        assert!(labels.len() == values.len());
+        // the gain of a daily observed series is already 1, the values are kept as they are
+        if labels.is_empty() || alpha >= 1.0 {
+            return;
+        }
+
+        let r = 1.0; // observation noise
+        let q = alpha * alpha / (1.0 - alpha) * r; // process noise per step
+
+        let mut p = r;
+        for k in 1..values.len() {
+            let steps = labels[k].steps(labels[k - 1]);
+            p += q * steps as f64;
+
+            let gain = p / (p + r);
+            values[k] = values[k - 1] * (1.0 - gain) + values[k] * gain;
+            p *= 1.0 - gain;
+        }
+    }
+
+    /// evaluates at each label the polynomial of `degree` fitted by weighted least
+    /// square to the values within `half_window` steps. `weight` receives the distance
+    /// normalized to [-1, 1].
+    fn local_polynomial<L, D>(
+        labels: &[L],
+        values: &mut [D],
+        half_window: f64,
+        degree: usize,
+        weight: impl Fn(f64) -> f64,
+    ) where
+        L: SmoothLabel,
+        D: SmoothValue,
+    {
+        assert!(labels.len() == values.len());
+        let original = values.to_vec();
+
+        let mut lo = 0;
+        let mut hi = 0;
+        for k in 0..labels.len() {
+            while labels[k].steps(labels[lo]) as f64 > half_window {
+                lo += 1;
+            }
+            while hi < labels.len() && labels[hi].steps(labels[k]) as f64 <= half_window {
+                hi += 1;
+            }
+
+            let distances: Vec<_> = (lo..hi)
+                .map(|j| labels[j].steps(labels[k]) as f64 / half_window)
+                .collect();
+            let weights: Vec<_> = distances.iter().map(|d| weight(*d)).collect();
+            let coefficients = Self::local_coefficients(&distances, &weights, degree);
+
+            values[k] = (lo..hi)
+                .map(|j| original[j] * coefficients[j - lo])
+                .reduce(|x, y| x + y)
+                .unwrap();
+        }
+    }
+
+    /// coefficients `l` so that the fitted value at distance 0 is `sum(l[j] * y[j])`,
+    /// lower the degree if there are not enough points to fit it.
+    fn local_coefficients(distances: &[f64], weights: &[f64], degree: usize) -> Vec<f64> {
+        let n = degree + 1;
+
+        // normal equations: (X^T W X) c = e_0, then l_j = w_j * sum_k c_k d_j^k
+        let mut a = vec![vec![0.0; n + 1]; n];
+        for (d, w) in distances.iter().zip(weights) {
+            for (r, row) in a.iter_mut().enumerate() {
+                for (c, x) in row.iter_mut().take(n).enumerate() {
+                    *x += w * d.powi((r + c) as i32);
+                }
+            }
+        }
+        a[0][n] = 1.0;
+
+        match calc::solve(a) {
+            Some(c) => distances
+                .iter()
+                .zip(weights)
+                .map(|(d, w)| w * (0..n).map(|k| c[k] * d.powi(k as i32)).sum::<f64>())
+                .collect(),
+            None if degree > 0 => Self::local_coefficients(distances, weights, degree - 1),
+            None => vec![1.0 / distances.len() as f64; distances.len()],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SmoothMethod, Smoother, MAX_WARM_UP_DAYS};
+    use crate::analyze::config::AnalysisConfig;
+    use chrono::{Datelike, Days, NaiveDate};
+
+    fn labels(days: &[u64]) -> Vec<NaiveDate> {
+        let day0 = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        days.iter().map(|d| day0 + Days::new(*d)).collect()
+    }
+
+    #[tokio::test]
+    async fn local_polynomial() {
+        let labels = labels(&[0, 1, 3, 4, 7, 8, 10]);
+        let line: Vec<_> = labels.iter().map(|d| 2.0 * d.ordinal0() as f64 + 1.0).collect();
+
+        // linear data are preserved by linear and quadratic fits
+        let mut res0 = line.clone();
+        Smoother::loess(&labels, &mut res0, 6.0);
+        let mut res1 = line.clone();
+        Smoother::savitzky_golay(&labels, &mut res1, 6.0);
+        for (x, y) in line.iter().zip(res0.iter().zip(res1.iter())) {
+            assert!((x - y.0).abs() < 1e-9);
+            assert!((x - y.1).abs() < 1e-9);
+        }
+
+        // constant data are preserved by every method
+        let mut res2 = vec![3.0; labels.len()];
+        Smoother::moving_average(&labels, &mut res2, 6.0);
+        Smoother::kalman(&labels, &mut res2, 0.1);
+        assert!(res2.iter().all(|x| (x - 3.0).abs() < 1e-9));
+    }
+
+    #[tokio::test]
+    async fn kalman_alpha_one() {
+        let labels = labels(&[0, 1, 3, 10]);
+        let mut values = vec![1.0, 2.0, 6.0, 5.0];
+        Smoother::kalman(&labels, &mut values, 1.0);
+        assert_eq!(values, vec![1.0, 2.0, 6.0, 5.0]);
+    }
+
+    #[tokio::test]
+    async fn warm_up_days() {
+        let config = AnalysisConfig::DEFAULT;
+        assert_eq!(SmoothMethod::Exponential.warm_up_days(&config), 92);
+        let config = AnalysisConfig {
+            smooth_alpha: 1e-300,
+            ..config
+        };
+        assert_eq!(SmoothMethod::Kalman.warm_up_days(&config), MAX_WARM_UP_DAYS);
+    }
+
+    #[tokio::test]
+    async fn moving_average() {
+        let labels = labels(&[0, 1, 2, 10]);
+        let mut values = vec![1.0, 2.0, 6.0, 5.0];
+        Smoother::moving_average(&labels, &mut values, 2.0);
+        assert_eq!(values, vec![1.5, 3.0, 4.0, 5.0]);
+    }
 }
@@ -0,0 +1,168 @@
+use super::config::AnalysisConfig;
+use super::non_pnp::PnpRemover;
+use crate::data::{CategoryCode, Invite, Pool};
+use chrono::Datelike;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Summary {
+    pub year: i32,
+    pub category: CategoryCode,
+    pub draws: usize,
+    pub invited: f64,
+    pub min_score: f64,
+    pub median_score: f64,
+    pub max_score: f64,
+    /// average number of days between two draws of the category within the year.
+    pub average_gap_days: Option<f64>,
+    /// share of the ITAs of the year.
+    pub share: f64,
+    pub pnp_invited: f64,
+    pub non_pnp_invited: f64,
+    /// differences with the same category of the previous year.
+    pub draws_delta: Option<i64>,
+    pub invited_delta: Option<f64>,
+}
+
+pub struct SummaryAnalyzer;
+
+impl SummaryAnalyzer {
+    // sorted by year, then by category
+    pub fn summarize(
+        config: &AnalysisConfig,
+        pool_data: &[Pool],
+        invite_data: &[Invite],
+    ) -> Vec<Summary> {
+        let non_pnp: HashMap<_, _> = PnpRemover::remove_pnp(config, pool_data, invite_data)
+            .into_iter()
+            .map(|invite| (invite.id, invite.size))
+            .collect();
+
+        let mut groups: BTreeMap<(i32, usize), Vec<&Invite>> = BTreeMap::new();
+        for invite in invite_data {
+            groups
+                .entry((invite.date.year(), invite.category.code as usize))
+                .or_default()
+                .push(invite);
+        }
+
+        let mut year_total: HashMap<i32, f64> = HashMap::new();
+        for invite in invite_data {
+            *year_total.entry(invite.date.year()).or_default() += invite.size;
+        }
+
+        let mut summaries: Vec<Summary> = Vec::with_capacity(groups.len());
+        for ((year, _), invites) in &groups {
+            let category = invites[0].category.code;
+            let invited = invites.iter().map(|invite| invite.size).sum::<f64>();
+
+            let mut scores: Vec<_> = invites.iter().map(|invite| invite.score).collect();
+            scores.sort_by(|x, y| x.total_cmp(y));
+            let median_score = if scores.len() % 2 == 1 {
+                scores[scores.len() / 2]
+            } else {
+                (scores[scores.len() / 2 - 1] + scores[scores.len() / 2]) / 2.0
+            };
+
+            let average_gap_days = if invites.len() > 1 {
+                let first = invites.first().unwrap().date;
+                let last = invites.last().unwrap().date;
+                Some((last - first).num_days() as f64 / (invites.len() - 1) as f64)
+            } else {
+                None
+            };
+
+            let non_pnp_invited = invites
+                .iter()
+                .map(|invite| non_pnp.get(&invite.id).copied().unwrap_or(0.0))
+                .sum::<f64>();
+
+            let previous = groups.get(&(year - 1, category as usize));
+
+            summaries.push(Summary {
+                year: *year,
+                category,
+                draws: invites.len(),
+                invited,
+                min_score: scores[0],
+                median_score,
+                max_score: scores[scores.len() - 1],
+                average_gap_days,
+                share: invited / year_total[year],
+                pnp_invited: invited - non_pnp_invited,
+                non_pnp_invited,
+                draws_delta: previous.map(|x| invites.len() as i64 - x.len() as i64),
+                invited_delta: previous
+                    .map(|x| invited - x.iter().map(|invite| invite.size).sum::<f64>()),
+            });
+        }
+
+        summaries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SummaryAnalyzer;
+    use crate::analyze::config::AnalysisConfig;
+    use crate::data::{Category, CategoryCode, Invite, InviteId, Pathway};
+    use chrono::NaiveDate;
+
+    fn invite(id: &str, date: (i32, u32, u32), name: &str, size: f64, score: f64) -> Invite {
+        Invite {
+            id: InviteId::parse(id),
+            date: NaiveDate::from_ymd_opt(date.0, date.1, date.2).unwrap(),
+            category: Category::parse(name),
+            pathway: Pathway::parse("Canadian Experience Class, Provincial Nominee Program"),
+            size,
+            score,
+            tie_break: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn summarize() {
+        let invite_data = vec![
+            invite("1", (2023, 1, 10), "General", 1000.0, 490.0),
+            invite(
+                "2",
+                (2023, 1, 24),
+                "Provincial Nominee Program",
+                500.0,
+                700.0,
+            ),
+            invite("3", (2023, 2, 7), "General", 3000.0, 480.0),
+            invite("4", (2023, 3, 13), "General", 2000.0, 470.0),
+            invite("5", (2024, 1, 9), "General", 1500.0, 500.0),
+        ];
+        let summaries = SummaryAnalyzer::summarize(&AnalysisConfig::default(), &[], &invite_data);
+
+        assert_eq!(summaries.len(), 3);
+
+        let general = &summaries[0];
+        assert_eq!(general.year, 2023);
+        assert_eq!(general.category, CategoryCode::General);
+        assert_eq!(general.draws, 3);
+        assert_eq!(general.invited, 6000.0);
+        assert_eq!(general.min_score, 470.0);
+        assert_eq!(general.median_score, 480.0);
+        assert_eq!(general.max_score, 490.0);
+        assert_eq!(general.average_gap_days, Some(31.0));
+        assert_eq!(general.share, 6000.0 / 6500.0);
+        assert_eq!(general.draws_delta, None);
+
+        let province = &summaries[1];
+        assert_eq!(province.category, CategoryCode::Province);
+        assert_eq!(province.pnp_invited, 500.0);
+        assert_eq!(province.non_pnp_invited, 0.0);
+        assert_eq!(province.average_gap_days, None);
+
+        let general = &summaries[2];
+        assert_eq!(general.year, 2024);
+        assert_eq!(general.share, 1.0);
+        assert_eq!(general.draws_delta, Some(-2));
+        assert_eq!(general.invited_delta, Some(-4500.0));
+    }
+}
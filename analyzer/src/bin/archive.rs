@@ -0,0 +1,29 @@
+//! Stores the current IRCC data into a local archive and optionally writes the
+//! changelog of the archived snapshots.
+//!
+//! usage: archive <archive dir> [changelog json]
+
+use analyzer::data::{raw_data, Archive};
+use chrono::Utc;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> std::io::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let dir = args
+        .next()
+        .expect("usage: archive <archive dir> [changelog json]");
+    let changelog_path = args.next();
+
+    let archive = Archive::open(dir)?;
+    match archive.store(raw_data().await, Utc::now())? {
+        Some(snapshot) => println!("stored {}", snapshot.path.display()),
+        None => println!("unchanged since the latest snapshot"),
+    }
+
+    if let Some(path) = changelog_path {
+        let changelog = archive.changelog()?;
+        std::fs::write(&path, serde_json::to_string_pretty(&changelog)?)?;
+        println!("{} changes written to {}", changelog.len(), path);
+    }
+    Ok(())
+}
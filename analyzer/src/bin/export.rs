@@ -0,0 +1,24 @@
+//! Writes the analysis as Parquet files, one per table.
+//!
+//! usage: export <output dir> [source url or path, or sqlite:<db>]
+
+use analyzer::data::{source_from_arg, DataSource};
+use analyzer::export::{record_batches, write_parquet};
+use std::path::Path;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<(), String> {
+    let mut args = std::env::args().skip(1);
+    let dir = args
+        .next()
+        .ok_or("usage: export <output dir> [source url or path, or sqlite:<db>]")?;
+    let (invite_data, pool_data, plan_data) = source_from_arg(args.next()).load().await?;
+
+    let batches =
+        record_batches(&pool_data, &invite_data, &plan_data).map_err(|e| e.to_string())?;
+    write_parquet(Path::new(&dir), &batches).map_err(|e| e.to_string())?;
+    for (name, batch) in &batches {
+        println!("{}: {} rows", name, batch.num_rows());
+    }
+    Ok(())
+}
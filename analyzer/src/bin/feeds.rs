@@ -0,0 +1,30 @@
+//! Writes the Atom feed and the iCalendar of the draws for the static site.
+//!
+//! usage: feeds <output dir> <site url>
+
+use analyzer::data::{all_plan, raw_data, Invite, Pool};
+use analyzer::feed::{atom_feed, icalendar};
+use std::path::Path;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> std::io::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let usage = "usage: feeds <output dir> <site url>";
+    let dir = args.next().expect(usage);
+    let site = args.next().expect(usage);
+
+    let raw_data = raw_data().await;
+    let invite_data = Invite::parse_all(raw_data);
+    let pool_data = Pool::parse_all(raw_data);
+    let plan_data = all_plan().await;
+
+    let dir = Path::new(&dir);
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(dir.join("draws.atom"), atom_feed(&invite_data, &site))?;
+    std::fs::write(
+        dir.join("draws.ics"),
+        icalendar(&pool_data, &invite_data, &plan_data),
+    )?;
+    println!("{} draws written to {}", invite_data.len(), dir.display());
+    Ok(())
+}
@@ -0,0 +1,52 @@
+//! Writes the json of every chart for the static site, the frontend shows them
+//! until the analyzer refreshes them in the browser.
+//!
+//! usage: prerender <output dir> [source url or path, or sqlite:<db>]
+
+use analyzer::chart::prerender::prerender;
+use analyzer::data::{source_from_arg, DataSource, ProvincialDraw, SnapshotDiff};
+use chrono::Utc;
+use std::path::Path;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<(), String> {
+    let mut args = std::env::args().skip(1);
+    let dir = args
+        .next()
+        .ok_or("usage: prerender <output dir> [source url or path, or sqlite:<db>]")?;
+    let (invite_data, pool_data, plan_data) = source_from_arg(args.next()).load().await?;
+
+    // written by the `archive` binary, absent unless an archive is kept
+    let dir = Path::new(&dir);
+    let changelog: Vec<SnapshotDiff> = std::fs::read_to_string(dir.join("changelog.json"))
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+    // the provincial draws are kept by hand, as json or csv
+    let provincial_data = match ["provincial.json", "provincial.csv"]
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.exists())
+    {
+        Some(path) => ProvincialDraw::read(&path)?,
+        None => Vec::new(),
+    };
+
+    let charts = prerender(
+        &pool_data,
+        &invite_data,
+        &plan_data,
+        &changelog,
+        &provincial_data,
+        Utc::now().timestamp_millis(),
+    )?;
+
+    let dir = dir.join("charts");
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    for (name, chart) in &charts {
+        let json = serde_json::to_string(chart).map_err(|e| e.to_string())?;
+        std::fs::write(dir.join(format!("{}.json", name)), json).map_err(|e| e.to_string())?;
+    }
+    println!("{} charts written to {}", charts.len(), dir.display());
+    Ok(())
+}
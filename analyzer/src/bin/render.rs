@@ -0,0 +1,60 @@
+//! Renders the charts of the dashboard as svg and png files.
+//!
+//! usage: render <output dir> [source url or path, or sqlite:<db>] [--light] [--range=<preset>]
+//!     [--provincial=<json or csv path>]
+
+use analyzer::data::{source_from_arg, DataSource, DateRange, ProvincialDraw};
+use analyzer::render::{figures, render_png, render_svg, Style};
+use std::path::Path;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<(), String> {
+    let (flags, mut args): (Vec<_>, Vec<_>) = std::env::args()
+        .skip(1)
+        .partition(|arg| arg.starts_with("--"));
+    let style = if flags.iter().any(|flag| flag == "--light") {
+        Style::LIGHT
+    } else {
+        Style::DARK
+    };
+    if args.is_empty() {
+        return Err(
+            "usage: render <output dir> [source url or path, or sqlite:<db>] [--light] \
+             [--range=<preset>] \
+             [--provincial=<path>]"
+                .into(),
+        );
+    }
+    let dir = args.remove(0);
+    let (invite_data, pool_data, plan_data) = source_from_arg(args.pop()).load().await?;
+    // e.g. --range=1y for the last year
+    let range = match flags.iter().find_map(|flag| flag.strip_prefix("--range=")) {
+        Some(preset) => DateRange::preset(preset, &invite_data)?,
+        None => DateRange::ALL,
+    };
+    // e.g. --provincial=provincial.csv
+    let provincial = flags
+        .iter()
+        .find_map(|flag| flag.strip_prefix("--provincial="));
+    let provincial_data = match provincial {
+        Some(path) => ProvincialDraw::read(Path::new(path))?,
+        None => Vec::new(),
+    };
+
+    let dir = Path::new(&dir);
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let figures = figures(
+        &pool_data,
+        &invite_data,
+        &plan_data,
+        &provincial_data,
+        range,
+    )?;
+    for (name, title, figure) in figures {
+        let svg = render_svg(&figure, &title, &style)?;
+        std::fs::write(dir.join(format!("{}.svg", name)), svg).map_err(|e| e.to_string())?;
+        render_png(&figure, &title, &style, &dir.join(format!("{}.png", name)))?;
+        println!("{}: {}", name, title);
+    }
+    Ok(())
+}
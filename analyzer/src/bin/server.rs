@@ -0,0 +1,22 @@
+//! Serves the chart data as json, see `analyzer::server::serve`.
+//!
+//! usage: server <address> [source url or path, or sqlite:<db>] [refresh interval secs]
+
+use analyzer::data::source_from_arg;
+use std::time::Duration;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> std::io::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let addr = args.next().expect(
+        "usage: server <address> [source url or path, or sqlite:<db>] [refresh interval secs]",
+    );
+    let source = args.next();
+    let interval = args
+        .next()
+        .map(|secs| secs.parse().expect("invalid refresh interval"))
+        .map(Duration::from_secs)
+        .unwrap_or(Duration::from_secs(3600));
+
+    analyzer::server::serve(addr, source_from_arg(source), interval).await
+}
@@ -0,0 +1,43 @@
+//! Imports the IRCC data into a SQLite database, or runs the analysis on it.
+//!
+//! usage: sqlite <db> import [source url or path]
+//!        sqlite <db> summary
+//!
+//! The other binaries analyze the tables with a source of `sqlite:<db>`.
+
+use analyzer::chart::summary::summary_json;
+use analyzer::data::{source_from_arg, DataSource, SqliteStore};
+
+const USAGE: &str = "usage: sqlite <db> import [source url or path] | sqlite <db> summary";
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<(), String> {
+    let mut args = std::env::args().skip(1);
+    let path = args.next().ok_or(USAGE)?;
+    let command = args.next().ok_or(USAGE)?;
+    let mut store = SqliteStore::open(&path).map_err(|e| e.to_string())?;
+
+    match command.as_str() {
+        "import" => {
+            let (invite_data, pool_data, plan_data) = source_from_arg(args.next()).load().await?;
+            store
+                .save_invites(&invite_data)
+                .and_then(|_| store.save_pools(&pool_data))
+                .and_then(|_| store.save_plans(&plan_data))
+                .map_err(|e| e.to_string())?;
+            println!(
+                "{} draws and {} pools saved to {}",
+                invite_data.len(),
+                pool_data.len(),
+                path
+            );
+        }
+        "summary" => {
+            let invite_data = store.load_invites().map_err(|e| e.to_string())?;
+            let pool_data = store.load_pools().map_err(|e| e.to_string())?;
+            println!("{}", summary_json(&pool_data, &invite_data));
+        }
+        _ => return Err(USAGE.into()),
+    }
+    Ok(())
+}
@@ -0,0 +1,31 @@
+//! Polls the IRCC data and notifies new draws and pool distributions.
+//!
+//! usage: watch <config json>, see `analyzer::watch::WatchConfig`.
+
+use analyzer::data::{source_from_arg, InviteId};
+use analyzer::watch::{WatchConfig, Watcher};
+use std::time::Duration;
+
+#[tokio::main(flavor = "current_thread")]
+async fn main() -> Result<(), String> {
+    let path = std::env::args()
+        .nth(1)
+        .ok_or("usage: watch <config json>")?;
+    let content = std::fs::read(&path).map_err(|e| format!("failed to read {}: {}", path, e))?;
+    let config: WatchConfig =
+        serde_json::from_slice(&content).map_err(|e| format!("failed to parse {}: {}", path, e))?;
+    let interval = Duration::from_secs(config.interval_secs);
+
+    let mut watcher = Watcher::new(source_from_arg(config.source.clone()), interval);
+    if let Some(since) = &config.since {
+        watcher = watcher.since(InviteId::parse(since));
+    }
+    config
+        .notifiers
+        .iter()
+        .cloned()
+        .fold(watcher, |watcher, notifier| watcher.notifier(notifier))
+        .run()
+        .await;
+    Ok(())
+}
@@ -1,16 +1,25 @@
 use super::dataset::{Dropdown, Label, PointStyle};
 use super::utils::Stacker;
 use crate::analyze::category::CategoryAnalyzer;
+use crate::analyze::composition::CompositionAnalyzer;
+use crate::analyze::config::AnalysisConfig;
 use crate::chart::dataset::{ChartData, LineDataset, Tooltip};
-use crate::chart::utils::{ToTimestamp, SERIALIZER};
-use crate::data::{CategoryCode, Invite, Pool};
+use crate::chart::utils::{date_range, thousands, ToTimestamp, SERIALIZER};
+use crate::data::{CategoryCode, DateRange, Invite, Pool};
+use chrono::NaiveDate;
 use itertools::Itertools;
 use serde::Serialize;
-use wasm_bindgen::prelude::*;
+use wasm_bindgen::{prelude::*, throw_str};
 
 #[wasm_bindgen]
 pub fn wasm_category_years(invite_data: *const Vec<Invite>) -> JsValue {
     let invite_data = unsafe { invite_data.as_ref().unwrap_throw() };
+    category_years(invite_data)
+        .serialize(&SERIALIZER)
+        .unwrap_throw()
+}
+
+pub fn category_years(invite_data: &[Invite]) -> Vec<Dropdown> {
     CategoryAnalyzer::of_category_years(invite_data)
         .keys()
         .sorted()
@@ -22,9 +31,15 @@ pub fn wasm_category_years(invite_data: *const Vec<Invite>) -> JsValue {
                 year.to_string()
             },
         })
-        .collect::<Vec<_>>()
-        .serialize(&SERIALIZER)
-        .unwrap_throw()
+        .collect()
+}
+
+// labels of the invitations before the range, walked for the pool they leave
+fn skip_warm_up(labels: &[NaiveDate], range: DateRange) -> usize {
+    range
+        .from
+        .map(|from| labels.partition_point(|date| *date < from))
+        .unwrap_or(0)
 }
 
 #[wasm_bindgen]
@@ -33,12 +48,53 @@ pub fn wasm_category_invite_data(
     invite_data: *const Vec<Invite>,
     category_year: f64,
     with_pnp: bool,
+    range: JsValue,
 ) -> JsValue {
     let pool_data = unsafe { pool_data.as_ref().unwrap_throw() };
     let invite_data = unsafe { invite_data.as_ref().unwrap_throw() };
-    let invite_data = CategoryAnalyzer::of_category_years(invite_data)[&(category_year as i32)];
-    let (category_invite_labels, mut category_invites, mut categories) =
-        CategoryAnalyzer::invite_per_category(pool_data, invite_data);
+    category_invite_data(
+        pool_data,
+        invite_data,
+        category_year as i32,
+        with_pnp,
+        date_range(range),
+    )
+    .unwrap_or_else(|e| throw_str(&e))
+    .serialize(&SERIALIZER)
+    .unwrap_throw()
+}
+
+pub fn category_invite_data(
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+    category_year: i32,
+    with_pnp: bool,
+    range: DateRange,
+) -> Result<ChartData<LineDataset>, String> {
+    let category_years = CategoryAnalyzer::of_category_years(invite_data);
+    let invite_data = category_years
+        .get(&category_year)
+        .ok_or_else(|| format!("invalid category year {}", category_year))?;
+    // the invitations are walked from the pool in effect at the start of the range,
+    // the ones before the range are then taken out of the cumulative counts
+    let warm_up = range.since_prior_pool(pool_data);
+    let (mut category_invite_labels, mut category_invites, mut categories) =
+        CategoryAnalyzer::invite_per_category(
+            &AnalysisConfig::current(),
+            warm_up.pools(pool_data),
+            warm_up.invites(invite_data),
+        );
+    let skip = skip_warm_up(&category_invite_labels, range);
+    if skip > 0 {
+        let earlier = category_invites[skip - 1];
+        category_invite_labels.drain(..skip);
+        category_invites = category_invites
+            .drain(skip..)
+            .map(|pool| pool - earlier)
+            .collect();
+        let last = category_invites.last().copied().unwrap_or_default();
+        categories.retain(|category| last[*category] != 0.0);
+    }
 
     if !with_pnp {
         category_invites
@@ -65,12 +121,12 @@ pub fn wasm_category_invite_data(
                 .collect();
 
             LineDataset {
-                label: category.as_str(),
+                label: Some(category.as_str()),
                 data,
                 background_color: category.as_color(),
                 border_color: category.as_color(),
                 fill: true,
-                point_style: PointStyle(None),
+                point_style: PointStyle::None,
                 ..Default::default()
             }
         })
@@ -99,16 +155,14 @@ pub fn wasm_category_invite_data(
         })
         .collect();
 
-    ChartData {
+    Ok(ChartData {
         labels,
         datasets,
         tooltip: Tooltip {
             title: vec![tooltip_title],
             label: tooltip_label,
         },
-    }
-    .serialize(&SERIALIZER)
-    .unwrap_throw()
+    })
 }
 
 #[wasm_bindgen]
@@ -116,12 +170,44 @@ pub fn wasm_category_pool_data(
     pool_data: *const Vec<Pool>,
     invite_data: *const Vec<Invite>,
     category_year: f64,
+    range: JsValue,
 ) -> JsValue {
     let pool_data = unsafe { pool_data.as_ref().unwrap_throw() };
     let invite_data = unsafe { invite_data.as_ref().unwrap_throw() };
-    let invite_data = CategoryAnalyzer::of_category_years(invite_data)[&(category_year as i32)];
-    let (category_invite_labels, category_invites, categories) =
-        CategoryAnalyzer::percent_per_category(pool_data, invite_data);
+    category_pool_data(
+        pool_data,
+        invite_data,
+        category_year as i32,
+        date_range(range),
+    )
+    .unwrap_or_else(|e| throw_str(&e))
+    .serialize(&SERIALIZER)
+    .unwrap_throw()
+}
+
+pub fn category_pool_data(
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+    category_year: i32,
+    range: DateRange,
+) -> Result<ChartData<LineDataset>, String> {
+    let category_years = CategoryAnalyzer::of_category_years(invite_data);
+    let invite_data = category_years
+        .get(&category_year)
+        .ok_or_else(|| format!("invalid category year {}", category_year))?;
+    let warm_up = range.since_prior_pool(pool_data);
+    let (mut category_invite_labels, mut category_invites, mut categories) =
+        CategoryAnalyzer::percent_per_category(
+            &AnalysisConfig::current(),
+            warm_up.pools(pool_data),
+            warm_up.invites(invite_data),
+        );
+    let skip = skip_warm_up(&category_invite_labels, range);
+    if skip > 0 {
+        category_invite_labels.drain(..skip);
+        category_invites.drain(..skip);
+        categories.retain(|category| category_invites.iter().any(|pool| pool[*category] != 0.0));
+    }
 
     let labels: Vec<_> = category_invite_labels
         .iter()
@@ -144,11 +230,11 @@ pub fn wasm_category_pool_data(
                 .collect();
 
             LineDataset {
-                label: category.as_str(),
+                label: Some(category.as_str()),
                 data,
                 background_color: category.as_color(),
                 border_color: category.as_color(),
-                point_style: PointStyle(None),
+                point_style: PointStyle::None,
                 ..Default::default()
             }
         })
@@ -170,14 +256,193 @@ pub fn wasm_category_pool_data(
         })
         .collect();
 
-    ChartData {
+    Ok(ChartData {
+        labels,
+        datasets,
+        tooltip: Tooltip {
+            title: vec![tooltip_title],
+            label: tooltip_label,
+        },
+    })
+}
+
+#[wasm_bindgen]
+pub fn wasm_category_composition_data(
+    pool_data: *const Vec<Pool>,
+    invite_data: *const Vec<Invite>,
+    category: String,
+    range: JsValue,
+) -> JsValue {
+    let pool_data = unsafe { pool_data.as_ref().unwrap_throw() };
+    let invite_data = unsafe { invite_data.as_ref().unwrap_throw() };
+    category_composition_data(pool_data, invite_data, &category, date_range(range))
+        .unwrap_or_else(|e| throw_str(&e))
+        .serialize(&SERIALIZER)
+        .unwrap_throw()
+}
+
+/// the candidates of each pool distribution estimated eligible for each category, `category`
+/// is "all" or the name of one, shown with the bounds of its estimate.
+pub fn category_composition_data(
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+    category: &str,
+    range: DateRange,
+) -> Result<ChartData<LineDataset>, String> {
+    let only = match category {
+        "all" => None,
+        name => Some(
+            CategoryCode::values()
+                .iter()
+                .find(|code| code.as_str().eq_ignore_ascii_case(name))
+                .copied()
+                .ok_or_else(|| format!("invalid category {}", name))?,
+        ),
+    };
+    let config = AnalysisConfig::current();
+    // the first distributions of the range are fitted to the draws before it
+    let warm_up = range
+        .warm_up(config.lookbehind_days)
+        .since_prior_pool(pool_data);
+    let composition: Vec<_> = CompositionAnalyzer::composition(
+        &config,
+        warm_up.pools(pool_data),
+        warm_up.invites(invite_data),
+    )
+    .into_iter()
+    .filter(|(date, _)| range.contains(*date))
+    .collect();
+
+    let categories: Vec<_> = composition
+        .iter()
+        .flat_map(|(_, estimates)| estimates.iter().map(|estimate| estimate.category))
+        .filter(|category| only.is_none_or(|only| only == *category))
+        .unique()
+        .sorted_by_key(|category| *category as usize)
+        .collect();
+
+    let labels: Vec<_> = composition
+        .iter()
+        .map(|(date, _)| Label::from(date.to_timestamp() as f64))
+        .collect();
+    let tooltip_title: Vec<_> = composition
+        .iter()
+        .map(|(date, _)| format!("{}", date.format("%Y-%m-%d")))
+        .collect();
+
+    let mut datasets = Vec::new();
+    let mut tooltip_label = Vec::new();
+    for category in categories {
+        let estimates: Vec<_> = composition
+            .iter()
+            .map(|(_, estimates)| estimates.iter().find(|x| x.category == category))
+            .collect();
+
+        let eligible = LineDataset {
+            label: Some(category.as_str()),
+            data: estimates.iter().map(|x| x.map(|x| x.eligible)).collect(),
+            background_color: category.as_color(),
+            border_color: category.as_color(),
+            point_style: PointStyle::None,
+            ..Default::default()
+        };
+        datasets.push(eligible.clone());
+        tooltip_label.push(
+            estimates
+                .iter()
+                .map(|x| match x {
+                    Some(x) if x.drains > 0 => format!(
+                        "{}: {} ({} - {}), {} confidence from {} draws, {} of {} pool changes agree",
+                        category.as_str(),
+                        thousands(x.eligible),
+                        thousands(x.low),
+                        thousands(x.high),
+                        x.confidence.as_str(),
+                        x.draws,
+                        x.drains_agreeing,
+                        x.drains
+                    ),
+                    Some(x) => format!(
+                        "{}: {} ({} - {}), {} confidence from {} draws",
+                        category.as_str(),
+                        thousands(x.eligible),
+                        thousands(x.low),
+                        thousands(x.high),
+                        x.confidence.as_str(),
+                        x.draws
+                    ),
+                    None => "".into(),
+                })
+                .collect(),
+        );
+
+        // the bounds of a single category
+        if only.is_some() {
+            for (name, bound) in [("Low", 0), ("High", 1)] {
+                let data: Vec<_> = estimates
+                    .iter()
+                    .map(|x| x.map(|x| if bound == 0 { x.low } else { x.high }))
+                    .collect();
+                tooltip_label.push(
+                    data.iter()
+                        .map(|count| match count {
+                            Some(count) => format!("{}: {}", name, thousands(*count)),
+                            None => "".into(),
+                        })
+                        .collect(),
+                );
+                datasets.push(LineDataset {
+                    label: None,
+                    data,
+                    border_dash: [5.0, 5.0],
+                    ..eligible.clone()
+                });
+            }
+        }
+    }
+
+    Ok(ChartData {
         labels,
         datasets,
         tooltip: Tooltip {
             title: vec![tooltip_title],
             label: tooltip_label,
         },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::category_composition_data;
+    use crate::data::test_util::{invites, pools};
+    use crate::data::DateRange;
+    use chrono::{Days, NaiveDate};
+
+    #[tokio::test]
+    async fn composition() {
+        let first = NaiveDate::from_ymd_opt(2024, 1, 10).unwrap();
+        let pool_data = pools(&[
+            (first, 1000.0),
+            (first + Days::new(14), 1000.0),
+            (first + Days::new(28), 1000.0),
+        ]);
+        let stem = "STEM occupations (2024-1)";
+        let invite_data = invites(&[
+            (first + Days::new(1), stem, 500.0, 500.0),
+            (first + Days::new(15), "General", 500.0, 480.0),
+        ]);
+
+        let range = DateRange::new(Some(first + Days::new(1)), None);
+        let chart = category_composition_data(&pool_data, &invite_data, "all", range).unwrap();
+        // the distribution before the draw is out of the range
+        assert_eq!(chart.labels.len(), 2);
+        assert_eq!(chart.datasets.len(), 1);
+        assert_eq!(chart.datasets[0].label.as_deref(), Some("STEM"));
+        assert!(chart.tooltip.label[0][0].starts_with("STEM: "));
+
+        let chart = category_composition_data(&pool_data, &invite_data, "stem", range).unwrap();
+        assert_eq!(chart.datasets.len(), 3);
+        assert_eq!(chart.tooltip.label.len(), 3);
+        assert!(category_composition_data(&pool_data, &invite_data, "x", range).is_err());
     }
-    .serialize(&SERIALIZER)
-    .unwrap_throw()
 }
@@ -0,0 +1,82 @@
+use super::dataset::{BarDataset, ChartData, Label, Tooltip};
+use crate::chart::utils::SERIALIZER;
+use crate::data::SnapshotDiff;
+use chrono::DateTime;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// `changelog` is the json written by the `archive` binary.
+#[wasm_bindgen]
+pub fn wasm_changelog_data(changelog: JsValue) -> JsValue {
+    let changelog: Vec<SnapshotDiff> = serde_wasm_bindgen::from_value(changelog).unwrap_throw();
+    changelog_data(&changelog)
+        .serialize(&SERIALIZER)
+        .unwrap_throw()
+}
+
+pub fn changelog_data(changelog: &[SnapshotDiff]) -> ChartData<BarDataset> {
+    let changelog: Vec<_> = changelog.iter().filter(|diff| !diff.is_empty()).collect();
+
+    let labels: Vec<_> = changelog
+        .iter()
+        .map(|diff| Label::from(diff.timestamp as f64))
+        .collect();
+
+    type Items = fn(&SnapshotDiff) -> Vec<String>;
+    let series: [(&str, &str, Items); 4] = [
+        ("Added", "#58D68D", |diff| diff.added.clone()),
+        ("Modified", "#F4D03F", |diff| {
+            diff.modified
+                .iter()
+                .map(|change| {
+                    let fields: Vec<_> = change.fields.iter().map(|x| x.field.as_str()).collect();
+                    format!("{} ({})", change.draw_number, fields.join(", "))
+                })
+                .collect()
+        }),
+        ("Removed", "#E74C3C", |diff| diff.removed.clone()),
+        ("New Pool", "#3498DB", |diff| diff.new_pools.clone()),
+    ];
+
+    let datasets: Vec<_> = series
+        .iter()
+        .map(|(label, color, items)| BarDataset {
+            label: Some((*label).into()),
+            data: changelog
+                .iter()
+                .map(|diff| Some(items(diff).len() as f64))
+                .collect(),
+            background_color: (*color).into(),
+            border_color: (*color).into(),
+            ..Default::default()
+        })
+        .collect();
+
+    let tooltip_title: Vec<_> = changelog
+        .iter()
+        .map(|diff| {
+            DateTime::from_timestamp_millis(diff.timestamp)
+                .map(|time| format!("{}", time.format("%Y-%m-%d %H:%M")))
+                .unwrap_or_default()
+        })
+        .collect();
+
+    let tooltip_label: Vec<_> = series
+        .iter()
+        .map(|(label, _, items)| {
+            changelog
+                .iter()
+                .map(|diff| format!("{}: {}", label, items(diff).join("; ")))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    ChartData {
+        labels,
+        datasets,
+        tooltip: Tooltip {
+            title: vec![tooltip_title],
+            label: tooltip_label,
+        },
+    }
+}
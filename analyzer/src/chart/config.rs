@@ -0,0 +1,26 @@
+use crate::analyze::config::AnalysisConfig;
+use crate::chart::utils::SERIALIZER;
+use serde::Serialize;
+use wasm_bindgen::{prelude::*, throw_str};
+
+#[wasm_bindgen]
+pub fn wasm_analysis_config() -> JsValue {
+    AnalysisConfig::current()
+        .serialize(&SERIALIZER)
+        .unwrap_throw()
+}
+
+#[wasm_bindgen]
+pub fn wasm_default_analysis_config() -> JsValue {
+    AnalysisConfig::default()
+        .serialize(&SERIALIZER)
+        .unwrap_throw()
+}
+
+#[wasm_bindgen]
+pub fn wasm_set_analysis_config(value: JsValue) {
+    let config: AnalysisConfig = serde_wasm_bindgen::from_value(value).unwrap_throw();
+    if let Err(message) = AnalysisConfig::set(config) {
+        throw_str(format!("invalid analysis config: {}", message).as_str())
+    }
+}
@@ -0,0 +1,219 @@
+use super::dataset::{
+    BarDataset, BarKind, BubbleDataset, ChartData, Label, LineDataset, PieDataset, PointStyle,
+    ScatterDataset, Tooltip, YAxis,
+};
+use serde::Serialize;
+
+/// How the values of a series are drawn.
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Mark {
+    Line,
+    /// a line filled down to the axis, the values are already stacked.
+    Area,
+    Bar,
+    /// one slice per label.
+    Arc,
+    /// a point at each x of the series, sized by its radius if any.
+    Point,
+}
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Series {
+    pub name: String,
+    pub mark: Mark,
+    /// one color per label for arcs, a single color otherwise.
+    pub colors: Vec<String>,
+    pub values: Vec<Option<f64>>,
+    /// the x of each value when the series has its own, empty if the values follow the labels.
+    pub xs: Vec<f64>,
+    /// radius of each point in pixels, empty for the default one.
+    pub radii: Vec<f64>,
+    pub dashed: bool,
+    pub points: bool,
+    /// series with the same stack are stacked on each other.
+    pub stack: Option<String>,
+    /// drawn against a second y axis, on the right.
+    pub secondary: bool,
+    pub legend: bool,
+    /// tooltip of each value, empty if the chart has none.
+    pub tooltips: Vec<String>,
+    /// tooltip title of each value when the series has its own, empty otherwise.
+    pub titles: Vec<String>,
+}
+
+/// Renderer-neutral description of a chart, the series share the labels of the x axis.
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Figure {
+    /// empty if every series has its own x.
+    pub labels: Vec<Label>,
+    pub series: Vec<Series>,
+    /// tooltip title of each label, empty if the chart has none.
+    pub titles: Vec<String>,
+    /// whether the x are timestamps (milliseconds).
+    pub temporal: bool,
+}
+
+impl Figure {
+    fn new<T: serde::Serialize>(
+        chart: ChartData<T>,
+        series: impl Fn(T, Vec<String>) -> Series,
+    ) -> Self {
+        let Tooltip { mut title, label } = chart.tooltip;
+        let mut tooltips = label.into_iter();
+        Self {
+            temporal: !chart.labels.is_empty()
+                && chart
+                    .labels
+                    .iter()
+                    .all(|label| matches!(label, Label::Number(_))),
+            labels: chart.labels,
+            series: chart
+                .datasets
+                .into_iter()
+                .map(|dataset| series(dataset, tooltips.next().unwrap_or_default()))
+                .collect(),
+            titles: if title.is_empty() {
+                Vec::new()
+            } else {
+                title.swap_remove(0)
+            },
+        }
+    }
+
+    // the tooltip titles are per series and value
+    fn points<T: serde::Serialize>(
+        chart: ChartData<T>,
+        temporal: bool,
+        series: impl Fn(T, Vec<String>, Vec<String>) -> Series,
+    ) -> Self {
+        let Tooltip { title, label } = chart.tooltip;
+        let (mut titles, mut tooltips) = (title.into_iter(), label.into_iter());
+        Self {
+            labels: Vec::new(),
+            series: chart
+                .datasets
+                .into_iter()
+                .map(|dataset| {
+                    series(
+                        dataset,
+                        tooltips.next().unwrap_or_default(),
+                        titles.next().unwrap_or_default(),
+                    )
+                })
+                .collect(),
+            titles: Vec::new(),
+            temporal,
+        }
+    }
+}
+
+impl From<ChartData<LineDataset>> for Figure {
+    fn from(chart: ChartData<LineDataset>) -> Self {
+        Self::new(chart, |dataset, tooltips| Series {
+            legend: dataset.label.is_some(),
+            name: dataset.label.unwrap_or_default(),
+            mark: if dataset.fill { Mark::Area } else { Mark::Line },
+            colors: vec![dataset.border_color],
+            values: dataset.data,
+            xs: Vec::new(),
+            radii: Vec::new(),
+            dashed: dataset.border_dash.iter().any(|x| *x != 0.0),
+            points: !matches!(dataset.point_style, PointStyle::None),
+            stack: None,
+            secondary: false,
+            tooltips,
+            titles: Vec::new(),
+        })
+    }
+}
+
+impl From<ChartData<BarDataset>> for Figure {
+    fn from(chart: ChartData<BarDataset>) -> Self {
+        Self::new(chart, |dataset, tooltips| {
+            let bar = dataset.kind == BarKind::Bar;
+            Series {
+                legend: dataset.label.is_some(),
+                name: dataset.label.unwrap_or_default(),
+                mark: if bar { Mark::Bar } else { Mark::Line },
+                colors: vec![dataset.background_color],
+                values: dataset.data,
+                xs: Vec::new(),
+                radii: Vec::new(),
+                dashed: false,
+                points: !bar,
+                stack: Some(dataset.stack).filter(|_| bar),
+                secondary: dataset.y_axis != YAxis::Primary,
+                tooltips,
+                titles: Vec::new(),
+            }
+        })
+    }
+}
+
+impl From<ChartData<PieDataset>> for Figure {
+    fn from(chart: ChartData<PieDataset>) -> Self {
+        Self::new(chart, |dataset, tooltips| Series {
+            legend: true,
+            name: dataset.label,
+            mark: Mark::Arc,
+            colors: dataset.background_color,
+            values: dataset.data.into_iter().map(Some).collect(),
+            xs: Vec::new(),
+            radii: Vec::new(),
+            dashed: false,
+            points: false,
+            stack: None,
+            secondary: false,
+            tooltips,
+            titles: Vec::new(),
+        })
+    }
+}
+
+impl From<ChartData<ScatterDataset>> for Figure {
+    fn from(chart: ChartData<ScatterDataset>) -> Self {
+        Self::points(chart, false, |dataset, tooltips, titles| Series {
+            legend: dataset.label.is_some(),
+            name: dataset.label.unwrap_or_default(),
+            mark: if dataset.show_line {
+                Mark::Line
+            } else {
+                Mark::Point
+            },
+            colors: vec![dataset.border_color],
+            values: dataset.data.iter().map(|point| Some(point.y)).collect(),
+            xs: dataset.data.iter().map(|point| point.x).collect(),
+            radii: Vec::new(),
+            dashed: false,
+            points: dataset.show_line && !matches!(dataset.point_style, PointStyle::None),
+            stack: None,
+            secondary: false,
+            tooltips,
+            titles,
+        })
+    }
+}
+
+// the bubbles are the draws at their date
+impl From<ChartData<BubbleDataset>> for Figure {
+    fn from(chart: ChartData<BubbleDataset>) -> Self {
+        Self::points(chart, true, |dataset, tooltips, titles| Series {
+            legend: dataset.label.is_some(),
+            name: dataset.label.unwrap_or_default(),
+            mark: Mark::Point,
+            colors: vec![dataset.border_color],
+            values: dataset.data.iter().map(|point| Some(point.y)).collect(),
+            xs: dataset.data.iter().map(|point| point.x).collect(),
+            radii: dataset.data.iter().map(|point| point.r).collect(),
+            dashed: false,
+            points: false,
+            stack: None,
+            secondary: false,
+            tooltips,
+            titles,
+        })
+    }
+}
@@ -1,29 +1,139 @@
-use super::dataset::Label;
-use crate::chart::dataset::{BarDataset, ChartData, LineDataset, Tooltip};
-use crate::chart::utils::{ToTimestamp, SERIALIZER};
-use crate::data::{CategoryCode, Invite};
-use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use super::dataset::{Label, PointStyle};
+use crate::analyze::anomaly::AnomalyAnalyzer;
+use crate::analyze::config::AnalysisConfig;
+use crate::analyze::non_pnp::PnpRemover;
+use crate::analyze::program::ProgramAnalyzer;
+use crate::analyze::smooth::{SmoothMethod, Smoother};
+use crate::chart::dataset::{
+    BarDataset, BarKind, BubbleDataset, BubblePoint, ChartData, LineDataset, Tooltip, YAxis,
+};
+use crate::chart::utils::{date_range, thousands, ToTimestamp, SERIALIZER};
+use crate::data::{CategoryCode, DateRange, Granularity, Invite, Pool};
+use chrono::{Datelike, Months, NaiveDate};
 use itertools::Itertools;
 use serde::Serialize;
 use std::collections::HashMap;
 use std::sync::{Mutex, OnceLock};
 use wasm_bindgen::{prelude::*, throw_str};
 
+// radius of the bubble of the largest draw, in pixels, the areas follow the sizes
+const MAX_BUBBLE_RADIUS: f64 = 20.0;
+
 #[wasm_bindgen]
-pub fn wasm_invite_score_data(invite_data: *const Vec<Invite>) -> JsValue {
+pub fn wasm_invite_score_data(
+    pool_data: *const Vec<Pool>,
+    invite_data: *const Vec<Invite>,
+    method: String,
+    split_pnp: bool,
+    range: JsValue,
+) -> JsValue {
+    let pool_data = unsafe { pool_data.as_ref().unwrap_throw() };
     let invite_data = unsafe { invite_data.as_ref().unwrap_throw() };
+    invite_score_data(
+        pool_data,
+        invite_data,
+        &method,
+        split_pnp,
+        date_range(range),
+    )
+    .unwrap_or_else(|e| throw_str(&e))
+    .serialize(&SERIALIZER)
+    .unwrap_throw()
+}
+
+/// estimated (PNP, non-PNP) split of the invitations within the range, if requested.
+fn pnp_splits(
+    config: &AnalysisConfig,
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+    range: DateRange,
+    split_pnp: bool,
+) -> Vec<Option<(f64, f64)>> {
+    let n = range.invites(invite_data).len();
+    if !split_pnp {
+        return vec![None; n];
+    }
+
+    // the pool in effect at the start of the range also gave the earlier invitations
+    let warm_up = range.since_prior_pool(pool_data);
+    let invites = warm_up.invites(invite_data);
+    let mut splits = PnpRemover::split_pnp(config, warm_up.pools(pool_data), invites);
+    splits.split_off(invites.len() - n)
+}
+
+pub fn invite_score_data(
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+    method: &str,
+    split_pnp: bool,
+    range: DateRange,
+) -> Result<ChartData<LineDataset>, String> {
+    let method =
+        SmoothMethod::parse(method).ok_or_else(|| format!("invalid smooth method {}", method))?;
+    let config = AnalysisConfig::current();
+    // the trend of the first invitations is smoothed with the earlier ones, dropped at the end
+    let warm_up = range.warm_up(method.warm_up_days(&config));
+    let skip = warm_up.invites(invite_data).len() - range.invites(invite_data).len();
+    let splits = pnp_splits(&config, pool_data, invite_data, warm_up, split_pnp);
+    // the draws are compared with all the earlier ones, including the ones before the range
+    let flagged: HashMap<_, _> = AnomalyAnalyzer::flagged(&config, invite_data)
+        .into_iter()
+        .map(|draw| (draw.invite.id, draw.anomalies))
+        .collect();
+    let invite_data = warm_up.invites(invite_data);
     let labels: Vec<_> = invite_data
         .iter()
         .map(|invitation| Label::from(invitation.date.to_timestamp() as f64))
         .collect();
-    let datasets: Vec<_> = CategoryCode::values()
+    let point_styles: Vec<String> = invite_data
+        .iter()
+        .map(|invitation| {
+            if flagged.contains_key(&invitation.id) {
+                "triangle".into()
+            } else {
+                "circle".into()
+            }
+        })
+        .collect();
+    let actual = CategoryCode::values().iter().map(|category| {
+        let data: Vec<_> = invite_data
+            .iter()
+            .map(|invitation| {
+                if invitation.category.code == *category {
+                    Some(invitation.score as f64)
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        LineDataset {
+            label: Some(category.as_str()),
+            data,
+            background_color: category.as_color(),
+            border_color: category.as_color(),
+            point_style: PointStyle::Each(point_styles.clone()),
+            ..Default::default()
+        }
+    });
+
+    let trend = CategoryCode::values()
         .iter()
+        .filter(|_| method != SmoothMethod::None)
         .map(|category| {
+            let (trend_labels, mut trend_data): (Vec<_>, Vec<_>) = invite_data
+                .iter()
+                .filter(|invitation| invitation.category.code == *category)
+                .map(|invitation| (invitation.date, invitation.score))
+                .unzip();
+            Smoother::smooth(&config, method, &trend_labels, &mut trend_data);
+
+            let mut trend_data = trend_data.into_iter();
             let data: Vec<_> = invite_data
                 .iter()
                 .map(|invitation| {
                     if invitation.category.code == *category {
-                        Some(invitation.score as f64)
+                        trend_data.next()
                     } else {
                         None
                     }
@@ -31,10 +141,95 @@ pub fn wasm_invite_score_data(invite_data: *const Vec<Invite>) -> JsValue {
                 .collect();
 
             LineDataset {
-                label: category.as_str(),
                 data,
                 background_color: category.as_color(),
                 border_color: category.as_color(),
+                border_dash: [5.0, 5.0],
+                point_style: PointStyle::None,
+                ..Default::default()
+            }
+        });
+
+    let datasets: Vec<_> = actual.chain(trend).collect();
+    let tooltip_title: Vec<_> = invite_data
+        .iter()
+        .map(|invitation| format!("{} ({})", invitation.date.format("%Y-%m-%d"), invitation.id))
+        .collect();
+
+    let tooltip_label: Vec<_> = CategoryCode::values()
+        .iter()
+        .map(|category| {
+            invite_data
+                .iter()
+                .zip(splits.iter())
+                .map(|(invitation, split)| {
+                    let label = match split {
+                        Some((pnp, non_pnp)) if *category == CategoryCode::General => format!(
+                            "{}: {} (PNP: {:.0}, non-PNP: {:.0})",
+                            category.as_str(),
+                            invitation.score,
+                            pnp,
+                            non_pnp
+                        ),
+                        _ => format!("{}: {}", category.as_str(), invitation.score),
+                    };
+                    match flagged.get(&invitation.id) {
+                        Some(anomalies) => {
+                            let explanations: Vec<_> = anomalies
+                                .iter()
+                                .map(|x| format!("{}: {}", x.kind.as_str(), x.explanation))
+                                .collect();
+                            format!("{} - {}", label, explanations.join("; "))
+                        }
+                        None => label,
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    Ok(ChartData {
+        labels,
+        datasets,
+        tooltip: Tooltip {
+            title: vec![tooltip_title],
+            label: tooltip_label,
+        },
+    }
+    .skip_labels(skip))
+}
+
+#[wasm_bindgen]
+pub fn wasm_invite_program_data(invite_data: *const Vec<Invite>, range: JsValue) -> JsValue {
+    let invite_data = unsafe { invite_data.as_ref().unwrap_throw() };
+    invite_program_data(invite_data, date_range(range))
+        .serialize(&SERIALIZER)
+        .unwrap_throw()
+}
+
+/// the cutoffs of the draws of each program, the draws open to several programs are "Mixed"
+/// whatever their occupational category.
+pub fn invite_program_data(invite_data: &[Invite], range: DateRange) -> ChartData<LineDataset> {
+    let invite_data = range.invites(invite_data);
+    let labels: Vec<_> = invite_data
+        .iter()
+        .map(|invitation| Label::from(invitation.date.to_timestamp() as f64))
+        .collect();
+    let programs = ProgramAnalyzer::invite_per_program(invite_data);
+
+    let datasets: Vec<_> = programs
+        .iter()
+        .map(|(program, _)| {
+            let data: Vec<_> = invite_data
+                .iter()
+                .map(|invitation| (invitation.program() == *program).then_some(invitation.score))
+                .collect();
+
+            LineDataset {
+                label: Some(program.as_str()),
+                data,
+                background_color: program.as_color(),
+                border_color: program.as_color(),
                 ..Default::default()
             }
         })
@@ -43,159 +238,509 @@ pub fn wasm_invite_score_data(invite_data: *const Vec<Invite>) -> JsValue {
         .iter()
         .map(|invitation| format!("{} ({})", invitation.date.format("%Y-%m-%d"), invitation.id))
         .collect();
+    let tooltip_label: Vec<_> = programs
+        .iter()
+        .map(|(program, _)| {
+            invite_data
+                .iter()
+                .map(|invitation| match invitation.occupation() {
+                    Some(occupation) => format!(
+                        "{} ({}): {}",
+                        program.as_str(),
+                        occupation.as_str(),
+                        invitation.score
+                    ),
+                    None => format!("{}: {}", program.as_str(), invitation.score),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
 
     ChartData {
         labels,
         datasets,
         tooltip: Tooltip {
             title: vec![tooltip_title],
-            label: Vec::new(),
+            label: tooltip_label,
         },
     }
-    .serialize(&SERIALIZER)
-    .unwrap_throw()
 }
 
 #[wasm_bindgen]
-pub fn wasm_invite_size_data(invite_data: *const Vec<Invite>, mode: String) -> JsValue {
+pub fn wasm_invite_bubble_data(invite_data: *const Vec<Invite>, range: JsValue) -> JsValue {
+    let invite_data = unsafe { invite_data.as_ref().unwrap_throw() };
+    invite_bubble_data(invite_data, date_range(range))
+        .serialize(&SERIALIZER)
+        .unwrap_throw()
+}
+
+/// every draw as a bubble at its date and cutoff, sized by its ITAs, one dataset per category.
+pub fn invite_bubble_data(invite_data: &[Invite], range: DateRange) -> ChartData<BubbleDataset> {
+    let invite_data = range.invites(invite_data);
+    let max_size = invite_data
+        .iter()
+        .map(|invitation| invitation.size)
+        .fold(0.0, f64::max);
+    let categories: Vec<_> = CategoryCode::values()
+        .iter()
+        .map(|category| {
+            let invites: Vec<_> = invite_data
+                .iter()
+                .filter(|invitation| invitation.category.code == *category)
+                .collect();
+            (category, invites)
+        })
+        .filter(|(_, invites)| !invites.is_empty())
+        .collect();
+
+    let datasets: Vec<_> = categories
+        .iter()
+        .map(|(category, invites)| BubbleDataset {
+            label: Some(category.as_str()),
+            data: invites
+                .iter()
+                .map(|invitation| BubblePoint {
+                    x: invitation.date.to_timestamp() as f64,
+                    y: invitation.score,
+                    r: MAX_BUBBLE_RADIUS * (invitation.size / max_size).sqrt(),
+                })
+                .collect(),
+            // translucent, the bubbles overlap
+            background_color: format!("{}80", category.as_color()),
+            border_color: category.as_color(),
+        })
+        .collect();
+    let tooltip_title: Vec<_> = categories
+        .iter()
+        .map(|(_, invites)| {
+            invites
+                .iter()
+                .map(|invitation| format!("{} ({})", invitation.date, invitation.id))
+                .collect()
+        })
+        .collect();
+    let tooltip_label: Vec<_> = categories
+        .iter()
+        .map(|(category, invites)| {
+            invites
+                .iter()
+                .map(|invitation| {
+                    format!(
+                        "{}: {}, {} ITAs",
+                        category.as_str(),
+                        invitation.score,
+                        thousands(invitation.size)
+                    )
+                })
+                .collect()
+        })
+        .collect();
+
+    ChartData {
+        labels: Vec::new(),
+        datasets,
+        tooltip: Tooltip {
+            title: tooltip_title,
+            label: tooltip_label,
+        },
+    }
+}
+
+#[wasm_bindgen]
+pub fn wasm_invite_size_data(
+    pool_data: *const Vec<Pool>,
+    invite_data: *const Vec<Invite>,
+    mode: String,
+    split_pnp: bool,
+    range: JsValue,
+) -> JsValue {
     static CACHE: Mutex<OnceLock<HashMap<String, ChartData<BarDataset>>>> =
         Mutex::new(OnceLock::new());
+    let config = AnalysisConfig::current();
+    let range = date_range(range);
+    let key = format!("{} {} {} {:?}", mode, split_pnp, config.pnp_score, range);
     {
         let mutex_guard = CACHE.lock().unwrap();
         let cache = mutex_guard.get_or_init(|| HashMap::new());
-        match cache.get(&mode) {
+        match cache.get(&key) {
             Some(value) => return value.serialize(&SERIALIZER).unwrap_throw(),
             None => (),
         }
     }
 
+    let pool_data = unsafe { pool_data.as_ref().unwrap_throw() };
     let invite_data = unsafe { invite_data.as_ref().unwrap_throw() };
-    fn per_day(date: NaiveDate) -> NaiveDate {
-        date
-    }
-    fn per_week(date: NaiveDate) -> NaiveDate {
-        date.week(Weekday::Mon).first_day()
-    }
-    fn per_month(date: NaiveDate) -> NaiveDate {
-        NaiveDate::from_ymd_opt(date.year(), date.month(), 1).unwrap()
+    let value = invite_size_data(pool_data, invite_data, &mode, split_pnp, range)
+        .unwrap_or_else(|e| throw_str(&e));
+    {
+        let mut mutex_guard = CACHE.lock().unwrap();
+        let cache = mutex_guard.get_mut().unwrap();
+        cache.insert(key.clone(), value);
+        cache[&key].serialize(&SERIALIZER).unwrap_throw()
     }
+}
 
-    let fn_bar_date = if mode == "d" {
-        per_day
-    } else if mode == "w" {
-        per_week
-    } else if mode == "m" {
-        per_month
-    } else {
-        throw_str(format!("invalid mode {}", mode).as_str())
+/// `mode` is a `Granularity` ("d", "w", "m", "q", "y", "fy" or "r<days>").
+pub fn invite_size_data(
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+    mode: &str,
+    split_pnp: bool,
+    range: DateRange,
+) -> Result<ChartData<BarDataset>, String> {
+    let granularity = Granularity::parse(mode).ok_or_else(|| format!("invalid mode {}", mode))?;
+    let config = AnalysisConfig::current();
+    let periods = granularity.periods(invite_data, range);
+    // the rolling windows reach before the range
+    let offset = periods
+        .first()
+        .map(|period| period.invites.start)
+        .unwrap_or(0);
+    let covered = match periods.first() {
+        Some(period) => DateRange::new(Some(invite_data[period.invites.start].date), range.to),
+        None => range,
     };
+    let splits = pnp_splits(&config, pool_data, invite_data, covered, split_pnp);
 
-    let labels: Vec<_> = invite_data
+    let labels: Vec<_> = periods
         .iter()
-        .group_by(|invitation| fn_bar_date(invitation.date))
-        .into_iter()
-        .map(|(bar_date, _)| Label::from(bar_date.to_timestamp() as f64))
+        .map(|period| Label::from(period.date.to_timestamp() as f64))
         .collect();
-    let datasets: Vec<_> = CategoryCode::values()
-        .iter()
-        .map(|category| {
-            let data: Vec<_> = invite_data
+    // General draws are broken into PNP and non-PNP portions if requested
+    let series = CategoryCode::values().iter().flat_map(|category| {
+        type Portion = fn(&Invite, &Option<(f64, f64)>) -> f64;
+        let all: Portion = |invitation, _| invitation.size;
+        let pnp: Portion = |_, split| split.map(|(pnp, _)| pnp).unwrap_or(0.0);
+        let non_pnp: Portion =
+            |invitation, split| split.map(|(_, non_pnp)| non_pnp).unwrap_or(invitation.size);
+
+        if split_pnp && *category == CategoryCode::General {
+            vec![
+                (
+                    *category,
+                    format!("{} (non-PNP)", category.as_str()),
+                    category.as_color(),
+                    non_pnp,
+                ),
+                (
+                    *category,
+                    format!("{} (PNP)", category.as_str()),
+                    CategoryCode::Province.as_color(),
+                    pnp,
+                ),
+            ]
+        } else {
+            vec![(*category, category.as_str(), category.as_color(), all)]
+        }
+    });
+    let mut datasets: Vec<_> = series
+        .map(|(category, label, color, portion)| {
+            let data: Vec<_> = periods
                 .iter()
-                .group_by(|invitation| fn_bar_date(invitation.date))
-                .into_iter()
-                .map(|(_, invitations)| {
+                .map(|period| {
                     Some(
-                        invitations
-                            .into_iter()
-                            .map(|invitation| {
-                                if invitation.category.code == *category {
-                                    invitation.size as f64
+                        period
+                            .invites
+                            .clone()
+                            .map(|i| {
+                                let invitation = &invite_data[i];
+                                if invitation.category.code == category {
+                                    portion(invitation, &splits[i - offset])
                                 } else {
                                     0_f64
                                 }
                             })
-                            .reduce(|x, y| x + y)
-                            .unwrap_or(0_f64),
+                            .sum(),
                     )
                 })
                 .collect();
 
             BarDataset {
-                label: category.as_str(),
+                label: Some(label),
                 data,
-                background_color: category.as_color(),
-                border_color: category.as_color(),
-                stack: "0".into(),
+                background_color: color.clone(),
+                border_color: color,
+                ..Default::default()
             }
         })
         .collect();
 
-    let tooltip_title: Vec<_> = invite_data
+    // the invitations of the year up to the end of each period, a yearly bar is the year already
+    if !matches!(granularity, Granularity::Year | Granularity::FiscalYear) {
+        let data: Vec<_> = periods
+            .iter()
+            .map(|period| {
+                let end = range.to.map_or(period.end, |to| to.min(period.end));
+                let year = DateRange::new(NaiveDate::from_ymd_opt(end.year(), 1, 1), Some(end));
+                Some(year.invites(invite_data).iter().map(|x| x.size).sum())
+            })
+            .collect();
+
+        datasets.push(BarDataset {
+            label: Some("Year to Date".into()),
+            data,
+            background_color: "#F1C40F".into(),
+            border_color: "#F1C40F".into(),
+            stack: "ytd".into(),
+            kind: BarKind::Line,
+            y_axis: YAxis::Secondary("ytd".into()),
+        });
+    }
+
+    let tooltip_title: Vec<_> = periods
         .iter()
-        .group_by(|invitation| fn_bar_date(invitation.date))
-        .into_iter()
-        .map(|(bar_date, invitations)| {
-            let id = match invitations.minmax() {
+        .map(|period| {
+            let id = match invite_data[period.invites.clone()].iter().minmax() {
                 itertools::MinMaxResult::NoElements => "123".into(),
                 itertools::MinMaxResult::OneElement(x) => format!("{}", x.id),
                 itertools::MinMaxResult::MinMax(x, y) => format!("{} - {}", x.id, y.id),
             };
 
-            let date = if mode == "d" {
-                format!("{}", bar_date.format("%Y-%m-%d"))
-            } else {
-                let bar_date2 = if mode == "w" {
-                    bar_date + Days::new(7)
-                } else {
-                    bar_date + Months::new(1)
-                };
-                format!(
-                    "{} - {}",
-                    bar_date.format("%Y-%m-%d"),
-                    bar_date2.format("%Y-%m-%d")
-                )
-            };
-
-            format!("{}({})", date, id)
+            format!("{}({})", granularity.describe(period), id)
         })
         .collect();
 
-    {
-        let mut mutex_guard = CACHE.lock().unwrap();
-        let cache = mutex_guard.get_mut().unwrap();
-        cache.insert(
-            mode.clone(),
-            ChartData {
-                labels,
-                datasets,
-                tooltip: Tooltip {
-                    title: vec![tooltip_title],
-                    label: Vec::new(),
-                },
-            },
-        );
-        cache[&mode].serialize(&SERIALIZER).unwrap_throw()
-    }
+    Ok(ChartData {
+        labels,
+        datasets,
+        tooltip: Tooltip {
+            title: vec![tooltip_title],
+            label: Vec::new(),
+        },
+    })
 }
 
 #[wasm_bindgen]
-pub fn wasm_invite_x_min(invite_data: *const Vec<Invite>) -> JsValue {
+pub fn wasm_invite_x_min(invite_data: *const Vec<Invite>, range: JsValue) -> JsValue {
     let invite_data = unsafe { invite_data.as_ref().unwrap_throw() };
-    invite_data
+    invite_x_min(invite_data, date_range(range))
+        .serialize(&SERIALIZER)
+        .unwrap()
+}
+
+pub fn invite_x_min(invite_data: &[Invite], range: DateRange) -> f64 {
+    range
+        .invites(invite_data)
         .first()
         .map(|invitation| (invitation.date - Months::new(1)).to_timestamp() as f64)
         .unwrap_or(0.0)
-        .serialize(&SERIALIZER)
-        .unwrap()
 }
 
 #[wasm_bindgen]
-pub fn wasm_invite_x_max(invite_data: *const Vec<Invite>) -> JsValue {
+pub fn wasm_invite_x_max(invite_data: *const Vec<Invite>, range: JsValue) -> JsValue {
     let invite_data = unsafe { invite_data.as_ref().unwrap_throw() };
-    invite_data
+    invite_x_max(invite_data, date_range(range))
+        .serialize(&SERIALIZER)
+        .unwrap()
+}
+
+pub fn invite_x_max(invite_data: &[Invite], range: DateRange) -> f64 {
+    range
+        .invites(invite_data)
         .last()
         .map(|invitation| (invitation.date + Months::new(1)).to_timestamp() as f64)
         .unwrap_or(0.0)
-        .serialize(&SERIALIZER)
-        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{invite_bubble_data, invite_program_data, invite_score_data, invite_size_data};
+    use crate::chart::dataset::{BarDataset, BarKind, ChartData, PointStyle, YAxis};
+    use crate::data::test_util::{invites, pools};
+    use crate::data::{Category, CategoryCode, DateRange, Invite, InviteId, Pathway};
+    use chrono::{Days, NaiveDate};
+
+    #[tokio::test]
+    async fn size_granularity() {
+        let day = |i| NaiveDate::from_ymd_opt(2024, 11, 1).unwrap() + Days::new(i);
+        let draws: Vec<_> = (0..6)
+            .map(|i| (day(20 * i), "General", 1000.0 * (i + 1) as f64, 500.0))
+            .collect();
+        let invite_data = invites(&draws);
+        let total = |chart: &ChartData<BarDataset>, i: usize| -> f64 {
+            chart
+                .datasets
+                .iter()
+                .filter(|dataset| dataset.kind == BarKind::Bar)
+                .map(|dataset| dataset.data[i].unwrap())
+                .sum()
+        };
+
+        // 2024-11-01, 11-21, 12-11, 12-31 | 2025-01-20, 02-09
+        let chart = invite_size_data(&[], &invite_data, "q", false, DateRange::ALL).unwrap();
+        assert_eq!(chart.labels.len(), 2);
+        assert_eq!((total(&chart, 0), total(&chart, 1)), (10000.0, 11000.0));
+        let ytd = chart.datasets.last().unwrap();
+        assert_eq!(ytd.y_axis, YAxis::Secondary("ytd".into()));
+        let json = serde_json::to_value(ytd).unwrap();
+        assert_eq!(json["type"], "line");
+        assert_eq!(json["yAxisID"], "ytd");
+        assert_eq!(ytd.data, vec![Some(10000.0), Some(11000.0)]);
+        assert_eq!(
+            chart.tooltip.title[0][1],
+            "2025 Q1 (2025-01-01 - 2025-03-31)(304 - 305)"
+        );
+
+        // the fiscal years have no year to date
+        let chart = invite_size_data(&[], &invite_data, "fy", false, DateRange::ALL).unwrap();
+        assert!(chart.datasets.iter().all(|x| x.kind == BarKind::Bar));
+
+        // the year to date of a range counts the invitations of the year before it
+        let range = DateRange::new(Some(day(40)), Some(day(90)));
+        let chart = invite_size_data(&[], &invite_data, "r30", false, range).unwrap();
+        assert_eq!(chart.labels.len(), 3);
+        assert_eq!(total(&chart, 0), 5000.0);
+        let ytd = chart.datasets.last().unwrap();
+        assert_eq!(ytd.data, vec![Some(6000.0), Some(10000.0), Some(5000.0)]);
+        assert!(invite_size_data(&[], &invite_data, "x", false, range).is_err());
+    }
+
+    #[tokio::test]
+    async fn score_anomaly() {
+        let day = |i| NaiveDate::from_ymd_opt(2024, 11, 1).unwrap() + Days::new(i);
+        let invite_data = invites(&[
+            (day(0), "General", 1000.0, 500.0),
+            (day(14), "General", 1000.0, 500.0),
+            (day(28), "General", 1000.0, 500.0),
+            (day(42), "General", 3000.0, 500.0),
+        ]);
+
+        // the draws before the range are still compared with
+        let range = DateRange::new(Some(day(14)), None);
+        let chart = invite_score_data(&[], &invite_data, "none", false, range).unwrap();
+        let general = CategoryCode::values()
+            .iter()
+            .position(|x| *x == CategoryCode::General)
+            .unwrap();
+        match &chart.datasets[general].point_style {
+            PointStyle::Each(styles) => assert_eq!(styles, &["circle", "circle", "triangle"]),
+            _ => panic!("a style for each draw expected"),
+        }
+        assert_eq!(chart.tooltip.label[general][0], "General: 500");
+        assert_eq!(
+            chart.tooltip.label[general][2],
+            "General: 500 - size: 3000 is 3.0 times the median 1000 of the 3 previous General draws"
+        );
+    }
+
+    #[tokio::test]
+    async fn bubble() {
+        let day = |i| NaiveDate::from_ymd_opt(2024, 11, 1).unwrap() + Days::new(i);
+        let french = "French language proficiency (2024-1)";
+        let invite_data = invites(&[
+            (day(0), "General", 4000.0, 500.0),
+            (day(7), french, 1000.0, 490.0),
+            (day(14), "General", 1000.0, 480.0),
+        ]);
+
+        let chart = invite_bubble_data(&invite_data, DateRange::ALL);
+        assert!(chart.labels.is_empty());
+        let labels: Vec<_> = chart.datasets.iter().map(|x| x.label.as_deref()).collect();
+        assert_eq!(labels, vec![Some("General"), Some("French")]);
+        // the areas follow the sizes
+        let general = &chart.datasets[0].data;
+        assert_eq!((general[0].r, general[1].r), (20.0, 10.0));
+        assert_eq!(general[1].y, 480.0);
+        assert_eq!(chart.tooltip.title[1][0], "2024-11-08 (301)");
+        assert_eq!(chart.tooltip.label[0][1], "General: 480, 1,000 ITAs");
+
+        let chart = invite_bubble_data(&invite_data, DateRange::new(Some(day(14)), None));
+        assert_eq!(chart.datasets.len(), 1);
+        assert_eq!(chart.datasets[0].data[0].r, 20.0);
+    }
+
+    #[tokio::test]
+    async fn retain_label() {
+        let day = |i| NaiveDate::from_ymd_opt(2024, 11, 1).unwrap() + Days::new(i);
+        let french = "French language proficiency (2024-1)";
+        let invite_data = invites(&[
+            (day(0), "General", 4000.0, 500.0),
+            (day(7), french, 1000.0, 490.0),
+            (day(14), "General", 1000.0, 480.0),
+        ]);
+        let pool_data = pools(&[(day(0), 1000.0), (day(7), 1000.0), (day(14), 1000.0)]);
+
+        // the trend of the category is kept with it
+        let chart = invite_score_data(&[], &invite_data, "ema", false, DateRange::ALL).unwrap();
+        let chart = chart.retain_label("French");
+        let labels: Vec<_> = chart.datasets.iter().map(|x| x.label.as_deref()).collect();
+        assert_eq!(labels, vec![Some("French"), None]);
+        // the trend is left out of the legend of the frontend
+        let json = serde_json::to_value(&chart.datasets[1]).unwrap();
+        assert_eq!(json["label"], "none");
+        assert_eq!(chart.tooltip.label.len(), 1);
+        assert!(chart.tooltip.label[0][1].starts_with("French: 490 - first draw"));
+
+        // the portions of the category are kept, and the year to date still counts every draw
+        let chart = invite_size_data(&pool_data, &invite_data, "m", true, DateRange::ALL).unwrap();
+        let chart = chart.retain_label("General");
+        let labels: Vec<_> = chart.datasets.iter().map(|x| x.label.as_deref()).collect();
+        assert_eq!(
+            labels,
+            vec![
+                Some("General (non-PNP)"),
+                Some("General (PNP)"),
+                Some("Year to Date")
+            ]
+        );
+        assert_eq!(chart.datasets[2].data, vec![Some(6000.0)]);
+
+        let chart = invite_bubble_data(&invite_data, DateRange::ALL).retain_label("French");
+        assert_eq!(chart.datasets.len(), 1);
+        assert_eq!(chart.tooltip.title, vec![vec!["2024-11-08 (301)"]]);
+        assert_eq!(chart.tooltip.label, vec![vec!["French: 490, 1,000 ITAs"]]);
+    }
+
+    #[tokio::test]
+    async fn program() {
+        let day = |i| NaiveDate::from_ymd_opt(2023, 6, 1).unwrap() + Days::new(i);
+        let invite_data: Vec<_> = [
+            (
+                "Canadian Experience Class",
+                "Canadian Experience Class",
+                520.0,
+            ),
+            (
+                "Healthcare occupations (2023-1)",
+                "Federal Skilled Worker, Canadian Experience Class",
+                470.0,
+            ),
+            (
+                "Provincial Nominee Program",
+                "Provincial Nominee Program",
+                760.0,
+            ),
+            (
+                "General",
+                "Federal Skilled Worker, Canadian Experience Class",
+                490.0,
+            ),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, (name, pathway, score))| Invite {
+            id: InviteId::new(250 + i as i32, 0),
+            date: day(7 * i as u64),
+            category: Category::parse(name),
+            pathway: Pathway::parse(pathway),
+            size: 1000.0,
+            score: *score,
+            tie_break: None,
+        })
+        .collect();
+
+        let chart = invite_program_data(&invite_data, DateRange::ALL);
+        let labels: Vec<_> = chart.datasets.iter().map(|x| x.label.as_deref()).collect();
+        assert_eq!(labels, vec![Some("Mixed"), Some("CEC"), Some("PNP")]);
+        assert_eq!(
+            chart.datasets[0].data,
+            vec![None, Some(470.0), None, Some(490.0)]
+        );
+        assert_eq!(chart.tooltip.label[0][1], "Mixed (Health): 470");
+        assert_eq!(chart.tooltip.label[1][0], "CEC: 520");
+
+        // the programs without draws within the range are left out
+        let chart = invite_program_data(&invite_data, DateRange::new(Some(day(14)), None));
+        assert_eq!(chart.datasets.len(), 2);
+    }
 }
@@ -1,13 +1,25 @@
 pub mod category;
+pub mod changelog;
+pub mod config;
+pub mod figure;
 pub mod invite;
 pub mod plan;
 pub mod pool;
+pub mod provincial;
+pub mod prerender;
+pub mod range;
+pub mod regression;
+pub mod summary;
+pub mod vega;
 
 mod utils {
     use std::{fmt::Debug, ops::Index};
 
     use chrono::NaiveDate;
     use serde_wasm_bindgen::Serializer;
+    use wasm_bindgen::{throw_str, JsValue};
+
+    use crate::data::DateRange;
 
     pub trait ToTimestamp {
         fn to_timestamp(&self) -> i64;
@@ -21,6 +33,28 @@ mod utils {
 
     pub static SERIALIZER: Serializer = Serializer::new().serialize_missing_as_null(true);
 
+    /// range of the data given by the frontend, all the data if it gives none.
+    pub fn date_range(value: JsValue) -> DateRange {
+        if value.is_undefined() || value.is_null() {
+            return DateRange::ALL;
+        }
+        serde_wasm_bindgen::from_value(value)
+            .unwrap_or_else(|e| throw_str(&format!("invalid date range: {}", e)))
+    }
+
+    /// e.g. 12,345
+    pub fn thousands(x: f64) -> String {
+        let digits = format!("{:.0}", x.abs());
+        let grouped: Vec<_> = digits
+            .as_bytes()
+            .rchunks(3)
+            .rev()
+            .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+            .collect();
+        let sign = if x.round() < 0.0 { "-" } else { "" };
+        format!("{}{}", sign, grouped.join(","))
+    }
+
     #[derive(Debug, Clone, Copy, PartialEq)]
     pub struct Stacker<const N: usize, T>
     where
@@ -47,7 +81,7 @@ mod utils {
     }
 }
 
-mod dataset {
+pub mod dataset {
     use serde::Serialize;
 
     #[derive(Serialize, Clone, Debug)]
@@ -63,6 +97,72 @@ mod dataset {
         pub label: Vec<Vec<String>>,
     }
 
+    // a dataset without a label is left out of the legend, "none" for the frontend
+    fn legend_label<S>(label: &Option<String>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(label.as_deref().unwrap_or("none"))
+    }
+
+    impl<T: Serialize> ChartData<T> {
+        // the tooltip labels are per dataset, the datasets drawn last may have none
+        fn retain_datasets(&mut self, keep: &[bool]) {
+            let mut kept = keep.iter();
+            self.datasets.retain(|_| *kept.next().unwrap());
+            if self.tooltip.label.len() <= keep.len() {
+                let mut kept = keep.iter();
+                self.tooltip.label.retain(|_| *kept.next().unwrap());
+            }
+        }
+    }
+
+    impl ChartData<LineDataset> {
+        /// keeps the datasets with the given label and the unlabelled ones drawn in their color,
+        /// e.g. their trend, with their tooltip labels.
+        pub fn retain_label(mut self, label: &str) -> Self {
+            let colors: Vec<_> = self
+                .datasets
+                .iter()
+                .filter(|dataset| dataset.label.as_deref() == Some(label))
+                .map(|dataset| dataset.border_color.clone())
+                .collect();
+            let keep: Vec<_> = self
+                .datasets
+                .iter()
+                .map(|dataset| match &dataset.label {
+                    Some(name) => name == label,
+                    None => colors.contains(&dataset.border_color),
+                })
+                .collect();
+            self.retain_datasets(&keep);
+            self
+        }
+
+        /// drops the first `n` labels with their values and tooltips, the warm-up of the analysis.
+        pub fn skip_labels(mut self, n: usize) -> Self {
+            let len = self.labels.len();
+            let n = n.min(len);
+            self.labels.drain(..n);
+            for dataset in self.datasets.iter_mut() {
+                dataset.data.drain(..n.min(dataset.data.len()));
+                if let PointStyle::Each(styles) = &mut dataset.point_style {
+                    styles.drain(..n.min(styles.len()));
+                }
+            }
+            for tooltip in self
+                .tooltip
+                .title
+                .iter_mut()
+                .chain(self.tooltip.label.iter_mut())
+                .filter(|tooltip| tooltip.len() == len)
+            {
+                tooltip.drain(..n);
+            }
+            self
+        }
+    }
+
     impl Default for Tooltip {
         fn default() -> Self {
             Self {
@@ -74,7 +174,9 @@ mod dataset {
 
     #[derive(Serialize, Clone, Debug)]
     pub struct LineDataset {
-        pub label: String,
+        /// the name in the legend, none to leave the dataset out of it.
+        #[serde(serialize_with = "legend_label")]
+        pub label: Option<String>,
         pub data: Vec<Option<f64>>,
         #[serde(rename = "backgroundColor")]
         pub background_color: String,
@@ -95,7 +197,7 @@ mod dataset {
     impl Default for LineDataset {
         fn default() -> Self {
             Self {
-                label: "none".into(),
+                label: None,
                 data: Default::default(),
                 background_color: "#ffffff".into(),
                 border_color: "#ffffff".into(),
@@ -104,22 +206,30 @@ mod dataset {
                 cubic_interpolation_mode: "monotone".into(),
                 tension: 0.0,
                 fill: false,
-                point_style: PointStyle(Some("circle".into())),
+                point_style: PointStyle::All("circle".into()),
             }
         }
     }
 
     #[derive(Clone, Debug)]
-    pub struct PointStyle(pub Option<String>);
+    pub enum PointStyle {
+        /// no point drawn.
+        None,
+        /// the same style for all the points.
+        All(String),
+        /// a style for each point.
+        Each(Vec<String>),
+    }
 
     impl Serialize for PointStyle {
         fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
         where
             S: serde::Serializer,
         {
-            match &self.0 {
-                None => serializer.serialize_bool(false),
-                Some(s) => serializer.serialize_str(s.as_str()),
+            match self {
+                Self::None => serializer.serialize_bool(false),
+                Self::All(s) => serializer.serialize_str(s.as_str()),
+                Self::Each(styles) => styles.serialize(serializer),
             }
         }
     }
@@ -162,23 +272,77 @@ mod dataset {
 
     #[derive(Serialize, Clone, Debug)]
     pub struct BarDataset {
-        pub label: String,
+        /// the name in the legend, none to leave the dataset out of it.
+        #[serde(serialize_with = "legend_label")]
+        pub label: Option<String>,
         pub data: Vec<Option<f64>>,
         #[serde(rename = "backgroundColor")]
         pub background_color: String,
         #[serde(rename = "borderColor")]
         pub border_color: String,
         pub stack: String,
+        #[serde(rename = "type")]
+        pub kind: BarKind,
+        #[serde(rename = "yAxisID")]
+        pub y_axis: YAxis,
+    }
+
+    #[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+    #[serde(rename_all = "lowercase")]
+    pub enum BarKind {
+        Bar,
+        /// a line drawn over the bars.
+        Line,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum YAxis {
+        /// the axis on the left, "y".
+        Primary,
+        /// another axis of the chart on the right, by its id.
+        Secondary(String),
+    }
+
+    impl Serialize for YAxis {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            match self {
+                Self::Primary => serializer.serialize_str("y"),
+                Self::Secondary(id) => serializer.serialize_str(id),
+            }
+        }
+    }
+
+    impl ChartData<BarDataset> {
+        /// keeps the datasets with the given label or a portion of it, e.g. "General (PNP)", and
+        /// the lines drawn over the bars, e.g. the year to date.
+        pub fn retain_label(mut self, label: &str) -> Self {
+            let portion = format!("{} (", label);
+            let keep: Vec<_> = self
+                .datasets
+                .iter()
+                .map(|dataset| {
+                    let name = dataset.label.as_deref().unwrap_or_default();
+                    dataset.kind == BarKind::Line || name == label || name.starts_with(&portion)
+                })
+                .collect();
+            self.retain_datasets(&keep);
+            self
+        }
     }
 
     impl Default for BarDataset {
         fn default() -> Self {
             Self {
-                label: "none".into(),
+                label: None,
                 data: Default::default(),
                 background_color: "#ffffff".into(),
                 border_color: "#ffffff".into(),
                 stack: "0".into(),
+                kind: BarKind::Bar,
+                y_axis: YAxis::Primary,
             }
         }
     }
@@ -207,6 +371,116 @@ mod dataset {
         }
     }
 
+    #[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+    pub struct Point {
+        pub x: f64,
+        pub y: f64,
+    }
+
+    /// Points at their own x, the chart has no labels and the tooltips are per dataset and
+    /// point.
+    #[derive(Serialize, Clone, Debug)]
+    pub struct ScatterDataset {
+        /// the name in the legend, none to leave the dataset out of it.
+        #[serde(serialize_with = "legend_label")]
+        pub label: Option<String>,
+        pub data: Vec<Point>,
+        #[serde(rename = "backgroundColor")]
+        pub background_color: String,
+        #[serde(rename = "borderColor")]
+        pub border_color: String,
+        /// joins the points in their order.
+        #[serde(rename = "showLine")]
+        pub show_line: bool,
+        #[serde(rename = "pointStyle")]
+        pub point_style: PointStyle,
+    }
+
+    impl Default for ScatterDataset {
+        fn default() -> Self {
+            Self {
+                label: None,
+                data: Default::default(),
+                background_color: "#ffffff".into(),
+                border_color: "#ffffff".into(),
+                show_line: false,
+                point_style: PointStyle::All("circle".into()),
+            }
+        }
+    }
+
+    #[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+    pub struct BubblePoint {
+        pub x: f64,
+        pub y: f64,
+        /// radius in pixels.
+        pub r: f64,
+    }
+
+    /// Points at their own x sized by their radius, the tooltips are per dataset and point as
+    /// for `ScatterDataset`.
+    #[derive(Serialize, Clone, Debug)]
+    pub struct BubbleDataset {
+        /// the name in the legend, none to leave the dataset out of it.
+        #[serde(serialize_with = "legend_label")]
+        pub label: Option<String>,
+        pub data: Vec<BubblePoint>,
+        #[serde(rename = "backgroundColor")]
+        pub background_color: String,
+        #[serde(rename = "borderColor")]
+        pub border_color: String,
+    }
+
+    impl ChartData<BubbleDataset> {
+        /// keeps the datasets with the given label, with their tooltips.
+        pub fn retain_label(mut self, label: &str) -> Self {
+            let keep: Vec<_> = self
+                .datasets
+                .iter()
+                .map(|dataset| dataset.label.as_deref() == Some(label))
+                .collect();
+            // the tooltip titles are per dataset too
+            if self.tooltip.title.len() == keep.len() {
+                let mut kept = keep.iter();
+                self.tooltip.title.retain(|_| *kept.next().unwrap());
+            }
+            self.retain_datasets(&keep);
+            self
+        }
+    }
+
+    impl Default for BubbleDataset {
+        fn default() -> Self {
+            Self {
+                label: None,
+                data: Default::default(),
+                background_color: "#ffffff".into(),
+                border_color: "#ffffff".into(),
+            }
+        }
+    }
+
+    #[derive(Serialize, Clone, Debug)]
+    pub struct TableData {
+        pub columns: Vec<TableColumn>,
+        pub rows: Vec<Vec<String>>,
+    }
+
+    #[derive(Serialize, Clone, Debug)]
+    pub struct TableColumn {
+        pub title: String,
+        pub key: String,
+    }
+
+    impl TableColumn {
+        pub fn new(title: &str, key: &str) -> Self {
+            Self {
+                title: title.into(),
+                key: key.into(),
+            }
+        }
+    }
+
     #[derive(Serialize, Clone, Debug)]
     pub struct Dropdown {
         pub label: String,
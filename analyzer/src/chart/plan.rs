@@ -1,18 +1,20 @@
 use super::category;
-use super::dataset::{Label, PieDataset, PointStyle};
+use super::dataset::{BarDataset, Label, PieDataset, PointStyle};
 use crate::analyze::calc::CategoryPool;
-use crate::analyze::category::CategoryAnalyzer;
+use crate::analyze::category::{CategoryAnalyzer, CategoryProgress};
+use crate::analyze::config::AnalysisConfig;
 use crate::analyze::non_pnp::PnpRemover;
+use crate::analyze::pace::{PaceAnalyzer, PaceShape};
 use crate::analyze::predict::Predictor;
 use crate::analyze::rate::RateAnalyzer;
 use crate::chart::dataset::{ChartData, LineDataset, Tooltip};
-use crate::chart::utils::{ToTimestamp, SERIALIZER};
-use crate::data::{CategoryCode, Invite, Plan, Pool};
+use crate::chart::utils::{date_range, thousands, ToTimestamp, SERIALIZER};
+use crate::data::{CategoryCode, DateRange, Invite, Plan, Pool};
 use chrono::{Datelike, NaiveDate};
 use itertools::Itertools;
 use serde::Serialize;
 use std::collections::{HashMap, HashSet};
-use wasm_bindgen::prelude::*;
+use wasm_bindgen::{prelude::*, throw_str};
 
 trait FromYear {
     fn y(year: i32) -> Self;
@@ -24,16 +26,45 @@ impl FromYear for NaiveDate {
     }
 }
 
+// the non-PNP invitations within the range, split with the pool in effect at its start
+fn non_pnp_invites(pool_data: &[Pool], invite_data: &[Invite], range: DateRange) -> Vec<Invite> {
+    let warm_up = range.since_prior_pool(pool_data);
+    let mut invites = PnpRemover::remove_pnp(
+        &AnalysisConfig::current(),
+        warm_up.pools(pool_data),
+        warm_up.invites(invite_data),
+    );
+    invites.retain(|invite| range.contains(invite.date));
+    invites
+}
+
 #[wasm_bindgen]
 pub fn wasm_plan_size_data(
     pool_data: *const Vec<Pool>,
     invite_data: *const Vec<Invite>,
     plan_data: *const Vec<Plan>,
+    range: JsValue,
 ) -> JsValue {
     let invite_data = unsafe { invite_data.as_ref().unwrap_throw() };
     let pool_data = unsafe { pool_data.as_ref().unwrap_throw() };
     let plan_data = unsafe { plan_data.as_ref().unwrap_throw() };
-    let invite_data = PnpRemover::remove_pnp(pool_data, invite_data);
+    plan_size_data(pool_data, invite_data, plan_data, date_range(range))
+        .serialize(&SERIALIZER)
+        .unwrap_throw()
+}
+
+pub fn plan_size_data(
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+    plan_data: &[Plan],
+    range: DateRange,
+) -> ChartData<LineDataset> {
+    let invite_data = non_pnp_invites(pool_data, invite_data, range);
+    let plan_data: Vec<_> = plan_data
+        .iter()
+        .filter(|plan| range.contains_year(plan.year))
+        .copied()
+        .collect();
 
     let labels: Vec<_> = plan_data
         .iter()
@@ -44,7 +75,7 @@ pub fn wasm_plan_size_data(
         let data: Vec<_> = plan_data.iter().map(|plan| Some(plan.min)).collect();
 
         LineDataset {
-            label: "Planed".into(),
+            label: Some("Planed".into()),
             data,
             background_color: "#F4D03F".into(),
             border_color: "#F4D03F".into(),
@@ -79,7 +110,7 @@ pub fn wasm_plan_size_data(
             .collect();
 
         LineDataset {
-            label: "Actual".into(),
+            label: Some("Actual".into()),
             data,
             background_color: "#58D68D".into(),
             border_color: "#58D68D".into(),
@@ -136,8 +167,6 @@ pub fn wasm_plan_size_data(
             label: vec![tooltip_label_min, tooltip_label_max, tooltip_label_actual],
         },
     }
-    .serialize(&SERIALIZER)
-    .unwrap_throw()
 }
 
 #[wasm_bindgen]
@@ -146,12 +175,30 @@ pub fn wasm_plan_pie_data(
     invite_data: *const Vec<Invite>,
     plan_data: *const Vec<Plan>,
     year: f64,
+    range: JsValue,
 ) -> JsValue {
     let invite_data = unsafe { invite_data.as_ref().unwrap_throw() };
     let pool_data = unsafe { pool_data.as_ref().unwrap_throw() };
     let plan_data = unsafe { plan_data.as_ref().unwrap_throw() };
-    let invite_data = PnpRemover::remove_pnp(pool_data, invite_data);
-    let year = year as i32;
+    plan_pie_data(
+        pool_data,
+        invite_data,
+        plan_data,
+        year as i32,
+        date_range(range),
+    )
+    .serialize(&SERIALIZER)
+    .unwrap_throw()
+}
+
+pub fn plan_pie_data(
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+    plan_data: &[Plan],
+    year: i32,
+    range: DateRange,
+) -> ChartData<PieDataset> {
+    let invite_data = non_pnp_invites(pool_data, invite_data, range);
 
     let labels: Vec<_> = vec![Label::from("Used"), Label::from("Unused")];
 
@@ -196,22 +243,474 @@ pub fn wasm_plan_pie_data(
             label: vec![vec![label_used, label_unused]],
         },
     }
+}
+
+// e.g. "2,300 ITAs behind pace"
+fn describe_gap(gap: f64) -> String {
+    if gap.round() == 0.0 {
+        "on pace".into()
+    } else if gap > 0.0 {
+        format!("{} ITAs ahead of pace", thousands(gap))
+    } else {
+        format!("{} ITAs behind pace", thousands(-gap))
+    }
+}
+
+#[wasm_bindgen]
+pub fn wasm_plan_pace_data(
+    pool_data: *const Vec<Pool>,
+    invite_data: *const Vec<Invite>,
+    plan_data: *const Vec<Plan>,
+    year: f64,
+    shape: String,
+    range: JsValue,
+) -> JsValue {
+    let invite_data = unsafe { invite_data.as_ref().unwrap_throw() };
+    let pool_data = unsafe { pool_data.as_ref().unwrap_throw() };
+    let plan_data = unsafe { plan_data.as_ref().unwrap_throw() };
+    plan_pace_data(
+        pool_data,
+        invite_data,
+        plan_data,
+        year as i32,
+        &shape,
+        date_range(range),
+    )
+    .unwrap_or_else(|e| throw_str(&e))
+    .serialize(&SERIALIZER)
+    .unwrap_throw()
+}
+
+/// the cumulative non-PNP invitations of the year day by day against the pace of its plan,
+/// `shape` is "linear" or "historical", the previous years within the range are shown and
+/// shape the historical pace.
+pub fn plan_pace_data(
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+    plan_data: &[Plan],
+    year: i32,
+    shape: &str,
+    range: DateRange,
+) -> Result<ChartData<LineDataset>, String> {
+    let shape = PaceShape::parse(shape).ok_or_else(|| format!("invalid pace shape {}", shape))?;
+    let plan = plan_data
+        .iter()
+        .find(|plan| plan.year == year)
+        .ok_or_else(|| format!("no plan for {}", year))?;
+    // the year is counted from its start and projected from the history before
+    let invite_data = non_pnp_invites(pool_data, invite_data, DateRange::new(None, range.to));
+    let last = invite_data.last().map(|invite| invite.date);
+    let past_years: Vec<_> = invite_data
+        .iter()
+        .map(|invite| invite.date.year())
+        .dedup()
+        .filter(|past| *past < year && range.contains_year(*past))
+        .filter(|past| last.is_some_and(|last| last.year() > *past))
+        .collect();
+    let pace = PaceAnalyzer::pace(
+        &AnalysisConfig::current(),
+        &invite_data,
+        plan,
+        shape,
+        &past_years,
+    );
+
+    let dates: Vec<_> = (0..pace.actual.len())
+        .map(|day| NaiveDate::from_yo_opt(year, day as u32 + 1).unwrap())
+        .collect();
+    let labels: Vec<_> = dates
+        .iter()
+        .map(|date| Label::from(date.to_timestamp() as f64))
+        .collect();
+    let tooltip_title: Vec<_> = dates
+        .iter()
+        .map(|date| format!("{}", date.format("%Y-%m-%d")))
+        .collect();
+    let middle: Vec<_> = pace
+        .pace_min
+        .iter()
+        .zip(&pace.pace_max)
+        .map(|(min, max)| (min + max) / 2.0)
+        .collect();
+    let against_pace = |name: &str, data: &[Option<f64>]| -> Vec<String> {
+        data.iter()
+            .zip(&middle)
+            .map(|(count, middle)| match count {
+                Some(count) => format!(
+                    "{}: {} ({})",
+                    name,
+                    thousands(*count),
+                    describe_gap(count - middle)
+                ),
+                None => "".into(),
+            })
+            .collect()
+    };
+
+    let actual = LineDataset {
+        label: Some(match pace.gap() {
+            Some(gap) => format!("{} ({})", year, describe_gap(gap)),
+            None => format!("{}", year),
+        }),
+        data: pace.actual.clone(),
+        background_color: "#58D68D".into(),
+        border_color: "#58D68D".into(),
+        point_style: PointStyle::None,
+        ..Default::default()
+    };
+    let projected = LineDataset {
+        label: pace
+            .projected
+            .last()
+            .copied()
+            .flatten()
+            .map(|total| format!("Projected ({} by year end)", thousands(total))),
+        data: pace.projected.clone(),
+        background_color: "#58D68D".into(),
+        border_color: "#58D68D".into(),
+        border_dash: [5.0, 5.0],
+        point_style: PointStyle::None,
+        ..Default::default()
+    };
+    let pace_min = LineDataset {
+        label: Some("Pace".into()),
+        data: pace.pace_min.iter().copied().map(Some).collect(),
+        background_color: "#F4D03F".into(),
+        border_color: "#F4D03F".into(),
+        border_dash: [5.0, 5.0],
+        point_style: PointStyle::None,
+        ..Default::default()
+    };
+    let pace_max = LineDataset {
+        label: None,
+        data: pace.pace_max.iter().copied().map(Some).collect(),
+        ..pace_min.clone()
+    };
+
+    // the previous years by day of the year
+    let past = past_years.iter().rev().map(|past| {
+        let last = NaiveDate::from_yo_opt(past + 1, 1).unwrap();
+        let cumulative = PaceAnalyzer::cumulative(&invite_data, *past, last);
+        let data: Vec<_> = (0..dates.len())
+            .map(|day| cumulative[day.min(cumulative.len() - 1)])
+            .collect();
+        let tooltip: Vec<_> = data
+            .iter()
+            .map(|count| format!("{}: {}", past, thousands(count.unwrap_or(0.0))))
+            .collect();
+
+        (
+            LineDataset {
+                label: Some(format!("{}", past)),
+                data,
+                background_color: "#5D6D7E".into(),
+                border_color: "#5D6D7E".into(),
+                point_style: PointStyle::None,
+                ..Default::default()
+            },
+            tooltip,
+        )
+    });
+    let (past, past_tooltip): (Vec<_>, Vec<_>) = past.unzip();
+
+    let pace_tooltip: Vec<_> = pace
+        .pace_min
+        .iter()
+        .zip(&pace.pace_max)
+        .map(|(min, max)| format!("Pace: {} - {}", thousands(*min), thousands(*max)))
+        .collect();
+    let label = [
+        against_pace(&year.to_string(), &actual.data),
+        against_pace("Projected", &projected.data),
+        pace_tooltip.clone(),
+        pace_tooltip,
+    ]
+    .into_iter()
+    .chain(past_tooltip)
+    .collect();
+
+    Ok(ChartData {
+        labels,
+        datasets: [actual, projected, pace_min, pace_max]
+            .into_iter()
+            .chain(past)
+            .collect(),
+        tooltip: Tooltip {
+            title: vec![tooltip_title],
+            label,
+        },
+    })
+}
+
+// the invitations per category of the year, counted from its start up to the end of the range
+fn category_progress(
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+    plan: &Plan,
+    range: DateRange,
+) -> (Vec<NaiveDate>, Vec<CategoryPool>, Vec<CategoryProgress>) {
+    let range = DateRange::new(None, range.to);
+    CategoryAnalyzer::progress_per_category(
+        &AnalysisConfig::current(),
+        range.pools(pool_data),
+        range.invites(invite_data),
+        plan,
+    )
+}
+
+#[wasm_bindgen]
+pub fn wasm_category_progress_data(
+    pool_data: *const Vec<Pool>,
+    invite_data: *const Vec<Invite>,
+    plan_data: *const Vec<Plan>,
+    year: f64,
+    range: JsValue,
+) -> JsValue {
+    let invite_data = unsafe { invite_data.as_ref().unwrap_throw() };
+    let pool_data = unsafe { pool_data.as_ref().unwrap_throw() };
+    let plan_data = unsafe { plan_data.as_ref().unwrap_throw() };
+    category_progress_data(
+        pool_data,
+        invite_data,
+        plan_data,
+        year as i32,
+        date_range(range),
+    )
+    .unwrap_or_else(|e| throw_str(&e))
+    .serialize(&SERIALIZER)
+    .unwrap_throw()
+}
+
+/// the invitations of each category within the year against the target of its plan,
+/// stacked with what remains of the target.
+pub fn category_progress_data(
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+    plan_data: &[Plan],
+    year: i32,
+    range: DateRange,
+) -> Result<ChartData<BarDataset>, String> {
+    let plan = plan_data
+        .iter()
+        .find(|plan| plan.year == year)
+        .ok_or_else(|| format!("no plan for {}", year))?;
+    let (_, _, progress) = category_progress(pool_data, invite_data, plan, range);
+
+    let labels: Vec<_> = progress
+        .iter()
+        .map(|progress| Label::from(progress.category.as_str()))
+        .collect();
+    let invited = BarDataset {
+        label: Some("Invited".into()),
+        data: progress
+            .iter()
+            .map(|progress| Some(progress.invited))
+            .collect(),
+        background_color: "#58D68D".into(),
+        border_color: "#58D68D".into(),
+        ..Default::default()
+    };
+    let remaining = BarDataset {
+        label: Some("Remaining".into()),
+        data: progress
+            .iter()
+            .map(|progress| {
+                progress
+                    .target
+                    .map(|target| f64::max(0.0, target - progress.invited))
+            })
+            .collect(),
+        background_color: "#F4D03F".into(),
+        border_color: "#F4D03F".into(),
+        ..Default::default()
+    };
+
+    let tooltip_title: Vec<_> = progress
+        .iter()
+        .map(|progress| progress.category.as_str())
+        .collect();
+    let tooltip_label_invited: Vec<_> = progress
+        .iter()
+        .map(|progress| match (progress.target, progress.ratio()) {
+            (Some(target), Some(ratio)) => format!(
+                "Invited: {} ({:.2}% of {})",
+                thousands(progress.invited),
+                ratio * 100.0,
+                thousands(target)
+            ),
+            _ => format!("Invited: {} (no target)", thousands(progress.invited)),
+        })
+        .collect();
+    let tooltip_label_remaining: Vec<_> = remaining
+        .data
+        .iter()
+        .map(|count| match count {
+            Some(count) => format!("Remaining: {}", thousands(*count)),
+            None => "".into(),
+        })
+        .collect();
+
+    Ok(ChartData {
+        labels,
+        datasets: vec![invited, remaining],
+        tooltip: Tooltip {
+            title: vec![tooltip_title],
+            label: vec![tooltip_label_invited, tooltip_label_remaining],
+        },
+    })
+}
+
+#[wasm_bindgen]
+pub fn wasm_category_target_data(
+    pool_data: *const Vec<Pool>,
+    invite_data: *const Vec<Invite>,
+    plan_data: *const Vec<Plan>,
+    year: f64,
+    range: JsValue,
+) -> JsValue {
+    let invite_data = unsafe { invite_data.as_ref().unwrap_throw() };
+    let pool_data = unsafe { pool_data.as_ref().unwrap_throw() };
+    let plan_data = unsafe { plan_data.as_ref().unwrap_throw() };
+    category_target_data(
+        pool_data,
+        invite_data,
+        plan_data,
+        year as i32,
+        date_range(range),
+    )
+    .unwrap_or_else(|e| throw_str(&e))
     .serialize(&SERIALIZER)
     .unwrap_throw()
 }
 
+/// the cumulative invitations of each category at the draws of the year, a category with a
+/// target also gets the linear pace of the target in its color.
+pub fn category_target_data(
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+    plan_data: &[Plan],
+    year: i32,
+    range: DateRange,
+) -> Result<ChartData<LineDataset>, String> {
+    let plan = plan_data
+        .iter()
+        .find(|plan| plan.year == year)
+        .ok_or_else(|| format!("no plan for {}", year))?;
+    let (dates, values, progress) = category_progress(pool_data, invite_data, plan, range);
+    let days = (NaiveDate::from_yo_opt(year + 1, 1).unwrap()
+        - NaiveDate::from_yo_opt(year, 1).unwrap())
+    .num_days() as f64;
+
+    let labels: Vec<_> = dates
+        .iter()
+        .map(|date| Label::from(date.to_timestamp() as f64))
+        .collect();
+    let tooltip_title: Vec<_> = dates
+        .iter()
+        .map(|date| format!("{}", date.format("%Y-%m-%d")))
+        .collect();
+
+    let (datasets, label): (Vec<_>, Vec<_>) = progress
+        .iter()
+        .flat_map(|progress| {
+            let category = progress.category;
+            let data: Vec<_> = values.iter().map(|pool| Some(pool[category])).collect();
+            let tooltip: Vec<_> = data
+                .iter()
+                .map(|count| {
+                    let count = count.unwrap_or(0.0);
+                    match progress.target {
+                        Some(target) if target > 0.0 => format!(
+                            "{}: {} ({:.2}% of {})",
+                            category.as_str(),
+                            thousands(count),
+                            count / target * 100.0,
+                            thousands(target)
+                        ),
+                        _ => format!("{}: {}", category.as_str(), thousands(count)),
+                    }
+                })
+                .collect();
+            let invited = LineDataset {
+                label: Some(category.as_str()),
+                data,
+                background_color: category.as_color(),
+                border_color: category.as_color(),
+                point_style: PointStyle::None,
+                ..Default::default()
+            };
+
+            let pace = progress.target.map(|target| {
+                let data: Vec<_> = dates
+                    .iter()
+                    .map(|date| Some(target * date.ordinal() as f64 / days))
+                    .collect();
+                let tooltip: Vec<_> = data
+                    .iter()
+                    .map(|count| {
+                        format!(
+                            "{} target pace: {} of {}",
+                            category.as_str(),
+                            thousands(count.unwrap_or(0.0)),
+                            thousands(target)
+                        )
+                    })
+                    .collect();
+                (
+                    LineDataset {
+                        label: None,
+                        data,
+                        border_dash: [5.0, 5.0],
+                        ..invited.clone()
+                    },
+                    tooltip,
+                )
+            });
+
+            std::iter::once((invited, tooltip)).chain(pace)
+        })
+        .unzip();
+
+    Ok(ChartData {
+        labels,
+        datasets,
+        tooltip: Tooltip {
+            title: vec![tooltip_title],
+            label,
+        },
+    })
+}
+
 #[wasm_bindgen]
 pub fn wasm_predict_data(
     pool_data: *const Vec<Pool>,
     invite_data: *const Vec<Invite>,
     plan_data: *const Vec<Plan>,
+    range: JsValue,
 ) -> JsValue {
     let invite_data = unsafe { invite_data.as_ref().unwrap_throw() };
     let pool_data = unsafe { pool_data.as_ref().unwrap_throw() };
     let plan_data = unsafe { plan_data.as_ref().unwrap_throw() };
+    predict_data(pool_data, invite_data, plan_data, date_range(range))
+        .serialize(&SERIALIZER)
+        .unwrap_throw()
+}
 
-    let (pred_labels, pred_values, categories) =
-        Predictor::predict(pool_data, invite_data, plan_data);
+/// the draws predicted from the data up to the end of the range, the predictor looks
+/// behind by itself so the start of the range is ignored.
+pub fn predict_data(
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+    plan_data: &[Plan],
+    range: DateRange,
+) -> ChartData<LineDataset> {
+    let range = DateRange::new(None, range.to);
+    let (pred_labels, pred_values, categories) = Predictor::predict(
+        &AnalysisConfig::current(),
+        range.pools(pool_data),
+        range.invites(invite_data),
+        plan_data,
+    );
 
     let labels: Vec<_> = pred_labels
         .iter()
@@ -226,7 +725,7 @@ pub fn wasm_predict_data(
                 .collect();
 
             LineDataset {
-                label: category.as_str(),
+                label: Some(category.as_str()),
                 data,
                 background_color: category.as_color(),
                 border_color: category.as_color(),
@@ -248,6 +747,97 @@ pub fn wasm_predict_data(
             label: Vec::new(),
         },
     }
-    .serialize(&SERIALIZER)
-    .unwrap_throw()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{category_progress_data, category_target_data, plan_pace_data};
+    use crate::chart::utils::thousands;
+    use crate::data::test_util::{invites, pools};
+    use crate::data::{all_plan, CategoryCode, DateRange, Plan};
+    use chrono::{Days, NaiveDate};
+
+    #[tokio::test]
+    async fn pace() {
+        assert_eq!(thousands(1234567.4), "1,234,567");
+        assert_eq!(thousands(-2300.0), "-2,300");
+
+        // a draw of 1000 every 14 days in 2024 up to 03-28
+        let first = NaiveDate::from_ymd_opt(2024, 1, 4).unwrap();
+        let draws: Vec<_> = (0..7)
+            .map(|i| (first + Days::new(14 * i), "General", 1000.0, 500.0))
+            .collect();
+        let invite_data = invites(&draws);
+        let plan_data = vec![Plan::new(2024, 36600.0, 36600.0)];
+
+        let pace = |year, shape| {
+            plan_pace_data(&[], &invite_data, &plan_data, year, shape, DateRange::ALL)
+        };
+
+        let chart = pace(2024, "linear").unwrap();
+        assert_eq!(chart.labels.len(), 366);
+        // 88 days of 100 ITAs on pace
+        assert_eq!(
+            chart.datasets[0].label.as_deref(),
+            Some("2024 (1,800 ITAs behind pace)")
+        );
+        assert_eq!(
+            chart.datasets[1].label.as_deref(),
+            Some("Projected (26,000 by year end)")
+        );
+        assert_eq!(
+            chart.tooltip.label[0][87],
+            "2024: 7,000 (1,800 ITAs behind pace)"
+        );
+        assert!(pace(2023, "linear").is_err());
+        assert!(pace(2024, "x").is_err());
+    }
+
+    #[tokio::test]
+    async fn category_progress() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+        let french = "French language proficiency (2024-1)";
+        let invite_data = invites(&[
+            (date(2, 1), french, 500.0, 400.0),
+            (date(7, 1), french, 1000.0, 400.0),
+        ]);
+        let plan_data = vec![Plan::new(2024, 90000.0, 116000.0)
+            .with_target(CategoryCode::French, 6000.0)
+            .with_target(CategoryCode::Health, 3000.0)];
+
+        let pool_data = pools(&[(date(1, 1), 10000.0)]);
+
+        let chart =
+            category_progress_data(&pool_data, &invite_data, &plan_data, 2024, DateRange::ALL)
+                .unwrap();
+        assert_eq!(chart.datasets[0].data, vec![Some(0.0), Some(1500.0)]);
+        assert_eq!(chart.datasets[1].data, vec![Some(3000.0), Some(4500.0)]);
+        assert_eq!(chart.tooltip.title[0], vec!["Health", "French"]);
+        assert_eq!(
+            chart.tooltip.label[0][1],
+            "Invited: 1,500 (25.00% of 6,000)"
+        );
+
+        // the range ends before the second draw
+        let range = DateRange::new(None, Some(date(6, 30)));
+        let chart =
+            category_target_data(&pool_data, &invite_data, &plan_data, 2024, range).unwrap();
+        assert_eq!(chart.labels.len(), 1);
+        // Health and its pace, French and its pace
+        assert_eq!(chart.datasets.len(), 4);
+        assert_eq!(chart.datasets[2].data, vec![Some(500.0)]);
+        assert_eq!(chart.datasets[3].data, vec![Some(6000.0 * 32.0 / 366.0)]);
+        assert!(category_progress_data(&pool_data, &invite_data, &plan_data, 2023, range).is_err());
+
+        // the bundled plans carry the CEC target of 2025
+        let cec = NaiveDate::from_ymd_opt(2025, 3, 5).unwrap();
+        let invite_data = invites(&[(cec, "Canadian Experience Class", 8298.0, 500.0)]);
+        let plan_data = all_plan().await;
+        let chart =
+            category_target_data(&pool_data, &invite_data, &plan_data, 2025, DateRange::ALL)
+                .unwrap();
+        // CEC and its pace
+        assert_eq!(chart.datasets.len(), 2);
+        assert_eq!(chart.tooltip.label[0][0], "CEC: 8,298 (10.00% of 82,980)");
+    }
 }
@@ -1,15 +1,16 @@
 use super::dataset::{Label, PointStyle};
 use super::utils::Stacker;
+use crate::analyze::config::AnalysisConfig;
 use crate::analyze::rate::RateAnalyzer;
-use crate::analyze::smooth::Smoother;
+use crate::analyze::smooth::{SmoothMethod, Smoother};
 use crate::chart::dataset::{ChartData, LineDataset, Tooltip};
-use crate::chart::utils::{ToTimestamp, SERIALIZER};
-use crate::data::{Invite, Pool};
+use crate::chart::utils::{date_range, ToTimestamp, SERIALIZER};
+use crate::data::{DateRange, Invite, Pool};
 use chrono::Days;
 use serde::Serialize;
 use std::iter;
 use std::ops::Mul;
-use wasm_bindgen::prelude::*;
+use wasm_bindgen::{prelude::*, throw_str};
 
 #[wasm_bindgen]
 pub fn wasm_pool_n() -> JsValue {
@@ -17,8 +18,15 @@ pub fn wasm_pool_n() -> JsValue {
 }
 
 #[wasm_bindgen]
-pub fn wasm_pool_count_data(pool_data: *const Vec<Pool>) -> JsValue {
+pub fn wasm_pool_count_data(pool_data: *const Vec<Pool>, range: JsValue) -> JsValue {
     let pool_data = unsafe { pool_data.as_ref().unwrap_throw() };
+    pool_count_data(pool_data, date_range(range))
+        .serialize(&SERIALIZER)
+        .unwrap_throw()
+}
+
+pub fn pool_count_data(pool_data: &[Pool], range: DateRange) -> ChartData<LineDataset> {
+    let pool_data = range.pools(pool_data);
     let labels: Vec<_> = pool_data
         .iter()
         .map(|pool| Label::from(pool.date.to_timestamp() as f64))
@@ -33,12 +41,12 @@ pub fn wasm_pool_count_data(pool_data: *const Vec<Pool>) -> JsValue {
                 .collect();
 
             LineDataset {
-                label: format!("{} - {}", Pool::min_score(i), Pool::max_score(i)),
+                label: Some(format!("{} - {}", Pool::min_score(i), Pool::max_score(i))),
                 data,
                 background_color: Pool::as_color(i),
                 border_color: Pool::as_color(i),
                 fill: true,
-                point_style: PointStyle(None),
+                point_style: PointStyle::None,
                 ..Default::default()
             }
         })
@@ -49,60 +57,107 @@ pub fn wasm_pool_count_data(pool_data: *const Vec<Pool>) -> JsValue {
         datasets,
         tooltip: Tooltip::default(),
     }
-    .serialize(&SERIALIZER)
-    .unwrap_throw()
 }
 
 #[wasm_bindgen]
-pub fn wasm_pool_count_y_max(pool_data: *const Vec<Pool>) -> JsValue {
-    let pool_date = unsafe { pool_data.as_ref().unwrap_throw() };
-    pool_date
+pub fn wasm_pool_count_y_max(pool_data: *const Vec<Pool>, range: JsValue) -> JsValue {
+    let pool_data = unsafe { pool_data.as_ref().unwrap_throw() };
+    pool_count_y_max(pool_data, date_range(range))
+        .serialize(&SERIALIZER)
+        .unwrap_throw()
+}
+
+pub fn pool_count_y_max(pool_data: &[Pool], range: DateRange) -> f64 {
+    range
+        .pools(pool_data)
         .iter()
         .map(|pool| pool.total())
         .max_by(|a, b| a.total_cmp(b))
         .unwrap_or(0.0)
         .mul(1.1)
-        .serialize(&SERIALIZER)
-        .unwrap_throw()
 }
 
 #[wasm_bindgen]
-pub fn wasm_pool_count_x_min(pool_data: *const Vec<Pool>) -> JsValue {
+pub fn wasm_pool_count_x_min(pool_data: *const Vec<Pool>, range: JsValue) -> JsValue {
     let pool_data = unsafe { pool_data.as_ref().unwrap_throw() };
-    pool_data
+    pool_count_x_min(pool_data, date_range(range))
+        .serialize(&SERIALIZER)
+        .unwrap_throw()
+}
+
+pub fn pool_count_x_min(pool_data: &[Pool], range: DateRange) -> f64 {
+    range
+        .pools(pool_data)
         .first()
         .map(|pool| pool.date.to_timestamp() as f64)
         .unwrap_or(0.0)
-        .serialize(&SERIALIZER)
-        .unwrap_throw()
 }
 
 #[wasm_bindgen]
-pub fn wasm_pool_count_x_max(pool_data: *const Vec<Pool>) -> JsValue {
+pub fn wasm_pool_count_x_max(pool_data: *const Vec<Pool>, range: JsValue) -> JsValue {
     let pool_data = unsafe { pool_data.as_ref().unwrap_throw() };
-    pool_data
+    pool_count_x_max(pool_data, date_range(range))
+        .serialize(&SERIALIZER)
+        .unwrap_throw()
+}
+
+pub fn pool_count_x_max(pool_data: &[Pool], range: DateRange) -> f64 {
+    range
+        .pools(pool_data)
         .last()
         .map(|pool| pool.date.to_timestamp() as f64)
         .unwrap_or(0.0)
-        .serialize(&SERIALIZER)
-        .unwrap_throw()
 }
 
 #[wasm_bindgen]
 pub fn wasm_pool_rate_data(
     pool_data: *const Vec<Pool>,
     invite_data: *const Vec<Invite>,
+    method: String,
+    range: JsValue,
 ) -> JsValue {
     let pool_data = unsafe { pool_data.as_ref().unwrap_throw() };
     let invite_data = unsafe { invite_data.as_ref().unwrap_throw() };
+    pool_rate_data(pool_data, invite_data, &method, date_range(range))
+        .unwrap_or_else(|e| throw_str(&e))
+        .serialize(&SERIALIZER)
+        .unwrap_throw()
+}
 
-    let (rate_labels, mut rate_data) = RateAnalyzer::pool_increase_rate(pool_data, invite_data);
-    let projected_rate = RateAnalyzer::projected_rate(&rate_labels, &rate_data);
-    Smoother::exponential(&rate_labels, &mut rate_data, 0.03278688524);
+pub fn pool_rate_data(
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+    method: &str,
+    range: DateRange,
+) -> Result<ChartData<LineDataset>, String> {
+    let method =
+        SmoothMethod::parse(method).ok_or_else(|| format!("invalid smooth method {}", method))?;
+
+    let config = AnalysisConfig::current();
+
+    // the rates and their trend are computed from earlier, the warm-up is dropped once smoothed
+    let warm_up = range
+        .warm_up(RateAnalyzer::warm_up_days(&config) + method.warm_up_days(&config))
+        .since_prior_pool(pool_data);
+    let (mut rate_labels, mut rate_data) = RateAnalyzer::pool_increase_rate(
+        &config,
+        warm_up.pools(pool_data),
+        warm_up.invites(invite_data),
+    );
+    let skip = range
+        .from
+        .map(|from| rate_labels.partition_point(|date| *date < from))
+        .unwrap_or(0);
+    let projected_rate =
+        RateAnalyzer::projected_rate(&config, &rate_labels[skip..], &rate_data[skip..]);
+    Smoother::smooth(&config, method, &rate_labels, &mut rate_data);
+    rate_labels.drain(..skip);
+    rate_data.drain(..skip);
 
     let labels: Vec<_> = {
-        assert!(!rate_labels.is_empty());
-        let last_day = *rate_labels.last().unwrap();
+        let last_day = *rate_labels
+            .last()
+            .ok_or_else(|| "no pool data within the date range".to_string())?;
 
         let extra_label = last_day + Days::new(120);
         rate_labels
@@ -119,11 +174,11 @@ pub fn wasm_pool_rate_data(
             .collect();
 
         LineDataset {
-            label: format!("> {}", Pool::min_score(i)),
+            label: Some(format!("> {}", Pool::min_score(i))),
             data,
             background_color: Pool::as_color(i),
             border_color: Pool::as_color(i),
-            point_style: PointStyle(None),
+            point_style: PointStyle::None,
             ..Default::default()
         }
     });
@@ -142,14 +197,14 @@ pub fn wasm_pool_rate_data(
             background_color: Pool::as_color(i),
             border_color: Pool::as_color(i),
             border_dash: [5.0, 5.0],
-            point_style: PointStyle(None),
+            point_style: PointStyle::None,
             ..Default::default()
         }
     });
 
     let datasets: Vec<_> = predict.chain(actual).collect();
 
-    ChartData {
+    Ok(ChartData {
         labels,
         datasets,
         tooltip: Tooltip {
@@ -165,29 +220,90 @@ pub fn wasm_pool_rate_data(
                 })
                 .collect(),
         },
-    }
-    .serialize(&SERIALIZER)
-    .unwrap_throw()
+    })
 }
 
 #[wasm_bindgen]
-pub fn wasm_pool_rate_x_min(pool_data: *const Vec<Pool>) -> JsValue {
+pub fn wasm_pool_rate_x_min(pool_data: *const Vec<Pool>, range: JsValue) -> JsValue {
     let pool_data = unsafe { pool_data.as_ref().unwrap_throw() };
+    pool_rate_x_min(pool_data, date_range(range))
+        .serialize(&SERIALIZER)
+        .unwrap_throw()
+}
+
+pub fn pool_rate_x_min(pool_data: &[Pool], range: DateRange) -> f64 {
+    let config = AnalysisConfig::current();
     pool_data
         .first()
-        .map(|pool| (pool.date + Days::new(RateAnalyzer::SUBMIT_DAYS as u64)).to_timestamp() as f64)
+        .map(|pool| pool.date + Days::new(RateAnalyzer::warm_up_days(&config)))
+        .map(|date| range.from.map_or(date, |from| from.max(date)))
+        .map(|date| date.to_timestamp() as f64)
         .unwrap_or(0.0)
-        .serialize(&SERIALIZER)
-        .unwrap_throw()
 }
 
 #[wasm_bindgen]
-pub fn wasm_pool_rate_x_max(pool_data: *const Vec<Pool>) -> JsValue {
+pub fn wasm_pool_rate_x_max(pool_data: *const Vec<Pool>, range: JsValue) -> JsValue {
     let pool_data = unsafe { pool_data.as_ref().unwrap_throw() };
-    pool_data
+    pool_rate_x_max(pool_data, date_range(range))
+        .serialize(&SERIALIZER)
+        .unwrap_throw()
+}
+
+pub fn pool_rate_x_max(pool_data: &[Pool], range: DateRange) -> f64 {
+    range
+        .pools(pool_data)
         .last()
         .map(|pool| (pool.date + Days::new(120)).to_timestamp() as f64)
         .unwrap_or(0.0)
-        .serialize(&SERIALIZER)
-        .unwrap_throw()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pool_rate_data;
+    use crate::chart::dataset::Label;
+    use crate::data::test_util::{invites, pools};
+    use crate::data::{DateRange, Pool};
+    use chrono::{Days, NaiveDate};
+
+    #[tokio::test]
+    async fn rate_warm_up() {
+        let day = |i| NaiveDate::from_ymd_opt(2024, 1, 3).unwrap() + Days::new(i);
+        let counts: Vec<_> = (0..26)
+            .map(|i| (day(14 * i), 1000.0 + 10.0 * (i * i) as f64))
+            .collect();
+        let pool_data = pools(&counts);
+        let draws: Vec<_> = (0..26)
+            .map(|i| (day(14 * i + 7), "General", 1500.0, 500.0))
+            .collect();
+        let invite_data = invites(&draws);
+
+        let all = pool_rate_data(&pool_data, &invite_data, "none", DateRange::ALL).unwrap();
+        let range = DateRange::new(Some(day(200)), Some(day(300)));
+        let part = pool_rate_data(&pool_data, &invite_data, "none", range).unwrap();
+
+        // the rates within the range do not depend on where the data start,
+        // but the last one, which has no next pool, and the projection
+        let timestamp = |label: &Label| match label {
+            Label::Number(value) => *value,
+            Label::String(_) => f64::NAN,
+        };
+        let offset = all
+            .labels
+            .iter()
+            .position(|label| timestamp(label) == timestamp(&part.labels[0]))
+            .unwrap();
+        let n = part.labels.len() - 2;
+        for (all, part) in all.datasets.iter().zip(&part.datasets).skip(Pool::N) {
+            for (x, y) in all.data[offset..offset + n].iter().zip(&part.data[..n]) {
+                assert!((x.unwrap() - y.unwrap()).abs() < 1e-6, "{:?} != {:?}", x, y);
+            }
+        }
+        assert!(pool_rate_data(
+            &pool_data,
+            &invite_data,
+            "none",
+            DateRange::new(None, Some(day(0)))
+        )
+        .is_err());
+    }
 }
@@ -0,0 +1,332 @@
+use super::category::{
+    category_composition_data, category_invite_data, category_pool_data, category_years,
+};
+use super::changelog::changelog_data;
+use super::dataset::Dropdown;
+use super::invite::{
+    invite_bubble_data, invite_program_data, invite_score_data, invite_size_data, invite_x_max,
+    invite_x_min,
+};
+use super::plan::{
+    category_progress_data, category_target_data, plan_pace_data, plan_pie_data, plan_size_data,
+    predict_data,
+};
+use super::pool::{
+    pool_count_data, pool_count_x_max, pool_count_x_min, pool_count_y_max, pool_rate_data,
+    pool_rate_x_max, pool_rate_x_min,
+};
+use super::provincial::provincial_inflow_data;
+use super::regression::cutoff_regression_data;
+use super::summary::summary_data;
+use crate::data::{DateRange, Invite, Plan, Pool, ProvincialDraw, SnapshotDiff};
+use chrono::Datelike;
+use serde::Serialize;
+use serde_json::Value;
+
+/// keys of `SmoothMethods` in the frontend.
+const METHODS: &[&str] = &["none", "ema", "ma", "loess", "sg", "kalman"];
+/// granularities of the invitation size chart, the zoom levels and the keys of `SizeGranularities`.
+const MODES: &[&str] = &["m", "w", "d", "q", "y", "fy", "r30", "r90"];
+/// keys of `PaceShapes` in the frontend.
+const PACE_SHAPES: &[&str] = &["linear", "historical"];
+/// keys of `CutoffVariables` in the frontend.
+const CUTOFF_VARIABLES: &[&str] = &["size", "gap", "pool"];
+
+/// Axis bounds and dropdown options the frontend needs before the charts.
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Meta {
+    /// timestamp (milliseconds) of the pre-rendering.
+    pub generated: i64,
+    pub invite_x_min: f64,
+    pub invite_x_max: f64,
+    pub pool_count_x_min: f64,
+    pub pool_count_x_max: f64,
+    pub pool_count_y_max: f64,
+    pub pool_rate_x_min: f64,
+    pub pool_rate_x_max: f64,
+    pub category_years: Vec<Dropdown>,
+}
+
+/// name of a pre-rendered chart, the chart followed by its arguments.
+pub fn chart_name(chart: &str, args: &[&dyn ToString]) -> String {
+    std::iter::once(chart.to_string())
+        .chain(args.iter().map(|arg| arg.to_string()))
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn to_value(value: impl Serialize) -> Result<Value, String> {
+    serde_json::to_value(value).map_err(|e| e.to_string())
+}
+
+/// Runs every chart producer with every option of the frontend, by chart name.
+pub fn prerender(
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+    plan_data: &[Plan],
+    changelog: &[SnapshotDiff],
+    provincial_data: &[ProvincialDraw],
+    generated: i64,
+) -> Result<Vec<(String, Value)>, String> {
+    let category_years = category_years(invite_data);
+    let mut charts = Vec::new();
+
+    for split_pnp in [false, true] {
+        for method in METHODS {
+            charts.push((
+                chart_name("invite_score", &[method, &split_pnp]),
+                to_value(invite_score_data(
+                    pool_data,
+                    invite_data,
+                    method,
+                    split_pnp,
+                    DateRange::ALL,
+                )?)?,
+            ));
+        }
+        for mode in MODES {
+            charts.push((
+                chart_name("invite_size", &[mode, &split_pnp]),
+                to_value(invite_size_data(
+                    pool_data,
+                    invite_data,
+                    mode,
+                    split_pnp,
+                    DateRange::ALL,
+                )?)?,
+            ));
+        }
+    }
+
+    charts.push((
+        chart_name("invite_program", &[]),
+        to_value(invite_program_data(invite_data, DateRange::ALL))?,
+    ));
+    charts.push((
+        chart_name("invite_bubble", &[]),
+        to_value(invite_bubble_data(invite_data, DateRange::ALL))?,
+    ));
+    for variable in CUTOFF_VARIABLES {
+        charts.push((
+            chart_name("cutoff_regression", &[variable]),
+            to_value(cutoff_regression_data(
+                pool_data,
+                invite_data,
+                variable,
+                DateRange::ALL,
+            )?)?,
+        ));
+    }
+
+    charts.push((
+        chart_name("pool_count", &[]),
+        to_value(pool_count_data(pool_data, DateRange::ALL))?,
+    ));
+    charts.push((
+        chart_name("provincial_inflow", &[]),
+        to_value(provincial_inflow_data(
+            pool_data,
+            invite_data,
+            provincial_data,
+            DateRange::ALL,
+        ))?,
+    ));
+    for method in METHODS {
+        charts.push((
+            chart_name("pool_rate", &[method]),
+            to_value(pool_rate_data(
+                pool_data,
+                invite_data,
+                method,
+                DateRange::ALL,
+            )?)?,
+        ));
+    }
+
+    for year in category_years.iter().map(|year| year.key as i32) {
+        for with_pnp in [false, true] {
+            charts.push((
+                chart_name("category_invite", &[&year, &with_pnp]),
+                to_value(category_invite_data(
+                    pool_data,
+                    invite_data,
+                    year,
+                    with_pnp,
+                    DateRange::ALL,
+                )?)?,
+            ));
+        }
+        charts.push((
+            chart_name("category_pool", &[&year]),
+            to_value(category_pool_data(
+                pool_data,
+                invite_data,
+                year,
+                DateRange::ALL,
+            )?)?,
+        ));
+    }
+    // the single categories are computed once the analyzer is loaded
+    charts.push((
+        chart_name("category_composition", &[&"all"]),
+        to_value(category_composition_data(
+            pool_data,
+            invite_data,
+            "all",
+            DateRange::ALL,
+        )?)?,
+    ));
+
+    charts.push((
+        chart_name("plan_size", &[]),
+        to_value(plan_size_data(
+            pool_data,
+            invite_data,
+            plan_data,
+            DateRange::ALL,
+        ))?,
+    ));
+    for plan in plan_data {
+        charts.push((
+            chart_name("plan_pie", &[&plan.year]),
+            to_value(plan_pie_data(
+                pool_data,
+                invite_data,
+                plan_data,
+                plan.year,
+                DateRange::ALL,
+            ))?,
+        ));
+    }
+    // only the year of the latest draw, the others are computed once the analyzer is loaded
+    let year = invite_data.last().map(|invite| invite.date.year());
+    if let Some(year) = year.filter(|year| plan_data.iter().any(|plan| plan.year == *year)) {
+        for shape in PACE_SHAPES {
+            charts.push((
+                chart_name("plan_pace", &[&year, shape]),
+                to_value(plan_pace_data(
+                    pool_data,
+                    invite_data,
+                    plan_data,
+                    year,
+                    shape,
+                    DateRange::ALL,
+                )?)?,
+            ));
+        }
+        charts.push((
+            chart_name("category_progress", &[&year]),
+            to_value(category_progress_data(
+                pool_data,
+                invite_data,
+                plan_data,
+                year,
+                DateRange::ALL,
+            )?)?,
+        ));
+        charts.push((
+            chart_name("category_target", &[&year]),
+            to_value(category_target_data(
+                pool_data,
+                invite_data,
+                plan_data,
+                year,
+                DateRange::ALL,
+            )?)?,
+        ));
+    }
+    charts.push((
+        chart_name("predict", &[]),
+        to_value(predict_data(
+            pool_data,
+            invite_data,
+            plan_data,
+            DateRange::ALL,
+        ))?,
+    ));
+
+    charts.push((
+        chart_name("summary", &[]),
+        to_value(summary_data(pool_data, invite_data, DateRange::ALL))?,
+    ));
+    charts.push((
+        chart_name("changelog", &[]),
+        to_value(changelog_data(changelog))?,
+    ));
+
+    let meta = Meta {
+        generated,
+        invite_x_min: invite_x_min(invite_data, DateRange::ALL),
+        invite_x_max: invite_x_max(invite_data, DateRange::ALL),
+        pool_count_x_min: pool_count_x_min(pool_data, DateRange::ALL),
+        pool_count_x_max: pool_count_x_max(pool_data, DateRange::ALL),
+        pool_count_y_max: pool_count_y_max(pool_data, DateRange::ALL),
+        pool_rate_x_min: pool_rate_x_min(pool_data, DateRange::ALL),
+        pool_rate_x_max: pool_rate_x_max(pool_data, DateRange::ALL),
+        category_years,
+    };
+    charts.push((chart_name("meta", &[]), to_value(meta)?));
+
+    Ok(charts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{chart_name, prerender};
+    use crate::data::test_util::{invites, pools};
+    use crate::data::Plan;
+    use chrono::NaiveDate;
+
+    #[tokio::test]
+    async fn charts() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+        let invite_data = invites(&[
+            (date(1, 10), "General", 1500.0, 520.0),
+            (date(1, 24), "General", 1500.0, 510.0),
+            (date(2, 7), "General", 1500.0, 500.0),
+        ]);
+        let pool_data = pools(&[
+            (date(1, 10), 1000.0),
+            (date(1, 24), 1100.0),
+            (date(2, 7), 1200.0),
+        ]);
+        let plan_data = vec![Plan::new(2024, 90000.0, 116000.0)];
+
+        let charts = prerender(&pool_data, &invite_data, &plan_data, &[], &[], 0).unwrap();
+        let names: Vec<_> = charts.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            chart_name("invite_score", &[&"ema", &true]),
+            "invite_score-ema-true"
+        );
+        for name in [
+            "invite_score-ema-true",
+            "invite_size-w-false",
+            "invite_program",
+            "invite_bubble",
+            "cutoff_regression-gap",
+            "pool_rate-loess",
+            "provincial_inflow",
+            "category_invite-0-true",
+            "category_composition-all",
+            "plan_pie-2024",
+            "plan_pace-2024-historical",
+            "category_progress-2024",
+            "summary",
+            "meta",
+        ] {
+            assert!(names.contains(&name), "{} is missing", name);
+        }
+
+        let (_, meta) = charts.last().unwrap();
+        assert_eq!(meta["categoryYears"][0]["label"], "all");
+        assert_eq!(
+            meta["inviteXMax"].as_f64().unwrap(),
+            date(3, 7)
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+                .and_utc()
+                .timestamp_millis() as f64
+        );
+    }
+}
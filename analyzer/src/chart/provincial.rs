@@ -0,0 +1,184 @@
+use super::dataset::{BarDataset, BarKind, ChartData, Label, Tooltip};
+use crate::analyze::config::AnalysisConfig;
+use crate::analyze::provincial::ProvincialAnalyzer;
+use crate::analyze::rate::RateAnalyzer;
+use crate::chart::utils::{date_range, thousands, ToTimestamp, SERIALIZER};
+use crate::data::{DateRange, Invite, Pool, Province, ProvincialDraw};
+use chrono::Days;
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+/// `provincial_data` is the json of the provincial draws, see `ProvincialDraw`.
+#[wasm_bindgen]
+pub fn wasm_provincial_inflow_data(
+    pool_data: *const Vec<Pool>,
+    invite_data: *const Vec<Invite>,
+    provincial_data: JsValue,
+    range: JsValue,
+) -> JsValue {
+    let pool_data = unsafe { pool_data.as_ref().unwrap_throw() };
+    let invite_data = unsafe { invite_data.as_ref().unwrap_throw() };
+    let provincial_data: Vec<ProvincialDraw> =
+        serde_wasm_bindgen::from_value(provincial_data).unwrap_throw();
+    provincial_inflow_data(pool_data, invite_data, &provincial_data, date_range(range))
+        .serialize(&SERIALIZER)
+        .unwrap_throw()
+}
+
+/// the weekly invitations of the provincial draws by province, against the candidates entering
+/// the PNP buckets of the pool, and the invitations shifted by the delay the inflow follows
+/// them the closest with.
+pub fn provincial_inflow_data(
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+    provincial_data: &[ProvincialDraw],
+    range: DateRange,
+) -> ChartData<BarDataset> {
+    let config = AnalysisConfig::current();
+    // the rates of the first weeks are measured from the pool distributions before the range
+    let warm_up = range
+        .warm_up(RateAnalyzer::warm_up_days(&config))
+        .since_prior_pool(pool_data);
+    let provincial_data: Vec<_> = provincial_data
+        .iter()
+        .filter(|draw| range.contains(draw.date))
+        .cloned()
+        .collect();
+    let weeks = ProvincialAnalyzer::weekly(
+        &config,
+        warm_up.pools(pool_data),
+        warm_up.invites(invite_data),
+        &provincial_data,
+    );
+    let weeks: Vec<_> = weeks
+        .into_iter()
+        .filter(|week| range.contains(week.start) || range.contains(week.start + Days::new(6)))
+        .collect();
+    let lag = ProvincialAnalyzer::lag(&weeks);
+
+    let labels: Vec<_> = weeks
+        .iter()
+        .map(|week| Label::from(week.start.to_timestamp() as f64))
+        .collect();
+
+    let provinces: Vec<_> = Province::values()
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| weeks.iter().any(|week| week.invited[*i] > 0.0))
+        .collect();
+    // (label, color, kind, value of each week)
+    let mut series: Vec<(String, String, BarKind, Vec<Option<f64>>)> = provinces
+        .iter()
+        .map(|(i, province)| {
+            let data = weeks.iter().map(|week| Some(week.invited[*i])).collect();
+            (
+                province.as_str().into(),
+                province.as_color(),
+                BarKind::Bar,
+                data,
+            )
+        })
+        .collect();
+    series.push((
+        format!("Inflow > {}", config.pnp_score),
+        "#9B59B6".into(),
+        BarKind::Line,
+        weeks.iter().map(|week| week.inflow).collect(),
+    ));
+    if let Some((lag, r)) = lag {
+        let data = std::iter::repeat_n(None, lag)
+            .chain(weeks.iter().map(|week| Some(week.total())))
+            .take(weeks.len())
+            .collect();
+        series.push((
+            format!("Invitations {} Weeks Earlier (r = {:.2})", lag, r),
+            "#ECF0F1".into(),
+            BarKind::Line,
+            data,
+        ));
+    }
+
+    let datasets: Vec<_> = series
+        .iter()
+        .map(|(label, color, kind, data)| BarDataset {
+            label: Some(label.clone()),
+            data: data.clone(),
+            background_color: color.clone(),
+            border_color: color.clone(),
+            // the bars of the provinces are stacked, the lines are not
+            stack: if *kind == BarKind::Bar {
+                "0".into()
+            } else {
+                label.clone()
+            },
+            kind: *kind,
+            ..Default::default()
+        })
+        .collect();
+    let tooltip_title: Vec<_> = weeks
+        .iter()
+        .map(|week| format!("Week of {}", week.start))
+        .collect();
+    let tooltip_label: Vec<_> = series
+        .iter()
+        .map(|(label, _, _, data)| {
+            data.iter()
+                .map(|x| match x {
+                    Some(x) => format!("{}: {}", label, thousands(*x)),
+                    None => format!("{}: -", label),
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    ChartData {
+        labels,
+        datasets,
+        tooltip: Tooltip {
+            title: vec![tooltip_title],
+            label: tooltip_label,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::provincial_inflow_data;
+    use crate::chart::dataset::BarKind;
+    use crate::data::{DateRange, Pool, Province, ProvincialDraw};
+    use chrono::{Days, NaiveDate};
+
+    #[tokio::test]
+    async fn provincial() {
+        let first = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let pool_data: Vec<_> = (0..20)
+            .map(|i| Pool {
+                data: [1000.0 + 10.0 * i as f64; Pool::N],
+                date: first + Days::new(7 * i),
+            })
+            .collect();
+        let provincial_data: Vec<_> = (0..20)
+            .map(|i| ProvincialDraw {
+                province: Province::Ontario,
+                date: first + Days::new(7 * i + 2),
+                size: 100.0 * ((i * 7) % 11) as f64,
+                min_score: None,
+                occupations: Vec::new(),
+            })
+            .collect();
+
+        let chart = provincial_inflow_data(&pool_data, &[], &provincial_data, DateRange::ALL);
+        assert_eq!(chart.labels.len(), 20);
+        let labels: Vec<_> = chart.datasets.iter().map(|x| x.label.as_deref()).collect();
+        assert_eq!(labels[..2], [Some("ON"), Some("Inflow > 600")]);
+        assert_eq!(chart.datasets[1].kind, BarKind::Line);
+        assert_eq!(chart.tooltip.label[0][3], "ON: 1,000");
+        assert_eq!(chart.tooltip.title[0][0], "Week of 2024-01-01");
+
+        // the weeks of the range only
+        let range = DateRange::new(Some(first + Days::new(70)), None);
+        let chart = provincial_inflow_data(&pool_data, &[], &provincial_data, range);
+        assert_eq!(chart.labels.len(), 10);
+        assert!(chart.datasets[1].data.iter().take(9).all(|x| x.is_some()));
+    }
+}
@@ -0,0 +1,14 @@
+use crate::chart::utils::SERIALIZER;
+use crate::data::{DateRange, Invite};
+use serde::Serialize;
+use wasm_bindgen::{prelude::*, throw_str};
+
+/// range of a preset ("all", "1y", "2y", "5y" or "categories"), to pass to the chart functions.
+#[wasm_bindgen]
+pub fn wasm_date_range(invite_data: *const Vec<Invite>, preset: String) -> JsValue {
+    let invite_data = unsafe { invite_data.as_ref().unwrap_throw() };
+    DateRange::preset(&preset, invite_data)
+        .unwrap_or_else(|e| throw_str(&e))
+        .serialize(&SERIALIZER)
+        .unwrap_throw()
+}
@@ -0,0 +1,188 @@
+use super::dataset::{ChartData, Point, PointStyle, ScatterDataset, Tooltip};
+use crate::analyze::regression::{CutoffVariable, RegressionAnalyzer};
+use crate::chart::utils::{date_range, thousands, SERIALIZER};
+use crate::data::{DateRange, Invite, Pool};
+use serde::Serialize;
+use wasm_bindgen::{prelude::*, throw_str};
+
+/// `variable` is "size", "gap" or "pool", see `CutoffVariable`.
+#[wasm_bindgen]
+pub fn wasm_cutoff_regression_data(
+    pool_data: *const Vec<Pool>,
+    invite_data: *const Vec<Invite>,
+    variable: String,
+    range: JsValue,
+) -> JsValue {
+    let pool_data = unsafe { pool_data.as_ref().unwrap_throw() };
+    let invite_data = unsafe { invite_data.as_ref().unwrap_throw() };
+    cutoff_regression_data(pool_data, invite_data, &variable, date_range(range))
+        .unwrap_or_else(|e| throw_str(&e))
+        .serialize(&SERIALIZER)
+        .unwrap_throw()
+}
+
+/// the quantity the coefficient is shown per, and what the variable counts.
+fn describe(variable: CutoffVariable) -> (f64, &'static str, &'static str) {
+    match variable {
+        CutoffVariable::Size => (1000.0, "1,000 ITAs", "ITAs"),
+        CutoffVariable::Gap => (1.0, "day", "days since the previous draw"),
+        CutoffVariable::Pool => (1000.0, "1,000 candidates", "candidates above 500"),
+    }
+}
+
+/// the cutoffs of the draws of each category against one variable of their regression, with
+/// the line fitted to them at the mean of the other variables.
+pub fn cutoff_regression_data(
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+    variable: &str,
+    range: DateRange,
+) -> Result<ChartData<ScatterDataset>, String> {
+    let variable =
+        CutoffVariable::parse(variable).ok_or_else(|| format!("invalid variable {}", variable))?;
+    let k = variable as usize;
+    let (unit, per, of) = describe(variable);
+    let regressions = RegressionAnalyzer::cutoff(pool_data, invite_data, range);
+
+    let mut datasets = Vec::new();
+    let mut tooltip_title = Vec::new();
+    let mut tooltip_label = Vec::new();
+    for regression in &regressions {
+        let category = regression.category;
+        let data: Vec<_> = regression
+            .samples
+            .iter()
+            .map(|sample| Point {
+                x: sample.x[k],
+                y: sample.invite.score,
+            })
+            .collect();
+        datasets.push(ScatterDataset {
+            label: Some(format!(
+                "{} (R² = {:.2})",
+                category.as_str(),
+                regression.r_squared
+            )),
+            data,
+            background_color: category.as_color(),
+            border_color: category.as_color(),
+            ..Default::default()
+        });
+        tooltip_title.push(
+            regression
+                .samples
+                .iter()
+                .map(|sample| format!("{} ({})", sample.invite.date, sample.invite.id))
+                .collect(),
+        );
+        tooltip_label.push(
+            regression
+                .samples
+                .iter()
+                .map(|sample| {
+                    format!(
+                        "{}: {}, {} {} (residual {:+.1})",
+                        category.as_str(),
+                        sample.invite.score,
+                        thousands(sample.x[k]),
+                        of,
+                        sample.residual
+                    )
+                })
+                .collect(),
+        );
+
+        // from the lowest to the highest value of the variable
+        let mut x = regression.mean();
+        let values = regression.samples.iter().map(|sample| sample.x[k]);
+        let bounds = [
+            values.clone().fold(f64::INFINITY, f64::min),
+            values.fold(f64::NEG_INFINITY, f64::max),
+        ];
+        let data: Vec<_> = bounds
+            .iter()
+            .map(|bound| {
+                x[k] = *bound;
+                Point {
+                    x: *bound,
+                    y: regression.fitted(&x),
+                }
+            })
+            .collect();
+        datasets.push(ScatterDataset {
+            data,
+            background_color: category.as_color(),
+            border_color: category.as_color(),
+            show_line: true,
+            point_style: PointStyle::None,
+            ..Default::default()
+        });
+        let fit = format!(
+            "{}: {:+.2} CRS per {}",
+            category.as_str(),
+            regression.coefficients[k] * unit,
+            per
+        );
+        tooltip_title.push(vec!["Fitted".into(); bounds.len()]);
+        tooltip_label.push(vec![fit; bounds.len()]);
+    }
+
+    Ok(ChartData {
+        labels: Vec::new(),
+        datasets,
+        tooltip: Tooltip {
+            title: tooltip_title,
+            label: tooltip_label,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cutoff_regression_data;
+    use crate::data::test_util::invites;
+    use crate::data::{DateRange, Pool};
+    use chrono::{Days, NaiveDate};
+
+    #[tokio::test]
+    async fn cutoff_regression() {
+        let first = NaiveDate::from_ymd_opt(2024, 1, 3).unwrap();
+        let (mut draws, mut pool_data) = (Vec::new(), Vec::new());
+        let mut date = first;
+        for i in 0..10 {
+            date = date + Days::new(7 + 7 * (i % 3) as u64);
+            let mut data = [1000.0; Pool::N];
+            data[Pool::N - 1] = 5000.0 + 1000.0 * ((i * 3) % 5) as f64;
+            pool_data.push(Pool { data, date });
+            let size = 1000.0 + 500.0 * (i % 4) as f64;
+            let score = 550.0 - size / 100.0 + ((i * 2) % 7) as f64;
+            draws.push((date, "General", size, score));
+        }
+        let invite_data = invites(&draws);
+
+        let chart = cutoff_regression_data(&pool_data, &invite_data, "size", DateRange::ALL);
+        let chart = chart.unwrap();
+        assert!(chart.labels.is_empty());
+        assert_eq!(chart.datasets.len(), 2);
+        let label = chart.datasets[0].label.as_deref().unwrap();
+        assert!(label.starts_with("General (R² = 0.9"));
+        assert_eq!(chart.datasets[0].data.len(), 9);
+        assert_eq!(chart.datasets[0].data[0].x, 1500.0);
+        assert_eq!(chart.datasets[0].data[0].y, 537.0);
+        assert_eq!(chart.tooltip.title[0][0], "2024-01-24 (301)");
+        assert!(chart.tooltip.label[0][0].starts_with("General: 537, 1,500 ITAs (residual "));
+
+        // the fitted line spans the sizes
+        let line = &chart.datasets[1];
+        assert!(line.show_line);
+        let xs: Vec<_> = line.data.iter().map(|point| point.x).collect();
+        assert_eq!(xs, vec![1000.0, 2500.0]);
+        assert!(line.data[0].y > line.data[1].y);
+        assert_eq!(
+            chart.tooltip.label[1][0],
+            "General: -9.09 CRS per 1,000 ITAs"
+        );
+
+        assert!(cutoff_regression_data(&pool_data, &invite_data, "x", DateRange::ALL).is_err());
+    }
+}
@@ -0,0 +1,82 @@
+use super::dataset::{TableColumn, TableData};
+use crate::analyze::config::AnalysisConfig;
+use crate::analyze::summary::SummaryAnalyzer;
+use crate::chart::utils::{date_range, SERIALIZER};
+use crate::data::{DateRange, Invite, Pool};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub fn wasm_summary_data(
+    pool_data: *const Vec<Pool>,
+    invite_data: *const Vec<Invite>,
+    range: JsValue,
+) -> JsValue {
+    let pool_data = unsafe { pool_data.as_ref().unwrap_throw() };
+    let invite_data = unsafe { invite_data.as_ref().unwrap_throw() };
+    summary_data(pool_data, invite_data, date_range(range))
+        .serialize(&SERIALIZER)
+        .unwrap_throw()
+}
+
+pub fn summary_data(pool_data: &[Pool], invite_data: &[Invite], range: DateRange) -> TableData {
+    let summaries = SummaryAnalyzer::summarize(
+        &AnalysisConfig::current(),
+        range.since_prior_pool(pool_data).pools(pool_data),
+        range.invites(invite_data),
+    );
+
+    let columns = vec![
+        TableColumn::new("Year", "year"),
+        TableColumn::new("Category", "category"),
+        TableColumn::new("Draws", "draws"),
+        TableColumn::new("ITAs", "invited"),
+        TableColumn::new("Share", "share"),
+        TableColumn::new("Min", "minScore"),
+        TableColumn::new("Median", "medianScore"),
+        TableColumn::new("Max", "maxScore"),
+        TableColumn::new("Avg. Gap (days)", "averageGapDays"),
+        TableColumn::new("PNP", "pnpInvited"),
+        TableColumn::new("Non-PNP", "nonPnpInvited"),
+        TableColumn::new("Draws YoY", "drawsDelta"),
+        TableColumn::new("ITAs YoY", "invitedDelta"),
+    ];
+
+    let rows = summaries
+        .iter()
+        .rev()
+        .map(|summary| {
+            vec![
+                format!("{}", summary.year),
+                summary.category.as_str(),
+                format!("{}", summary.draws),
+                format!("{}", summary.invited),
+                format!("{:.2}%", summary.share * 100.0),
+                format!("{}", summary.min_score),
+                format!("{}", summary.median_score),
+                format!("{}", summary.max_score),
+                summary
+                    .average_gap_days
+                    .map(|days| format!("{:.1}", days))
+                    .unwrap_or_default(),
+                format!("{:.0}", summary.pnp_invited),
+                format!("{:.0}", summary.non_pnp_invited),
+                summary
+                    .draws_delta
+                    .map(|delta| format!("{:+}", delta))
+                    .unwrap_or_default(),
+                summary
+                    .invited_delta
+                    .map(|delta| format!("{:+}", delta))
+                    .unwrap_or_default(),
+            ]
+        })
+        .collect();
+
+    TableData { columns, rows }
+}
+
+pub fn summary_json(pool_data: &[Pool], invite_data: &[Invite]) -> String {
+    let summaries = SummaryAnalyzer::summarize(&AnalysisConfig::current(), pool_data, invite_data);
+    serde_json::to_string_pretty(&summaries).unwrap()
+}
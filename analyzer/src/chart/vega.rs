@@ -0,0 +1,430 @@
+use super::dataset::Label;
+use super::figure::{Figure, Mark, Series};
+use serde_json::{json, Map, Value};
+
+const SCHEMA: &str = "https://vega.github.io/schema/vega-lite/v5.json";
+
+fn label_value(label: &Label) -> Value {
+    match label {
+        Label::String(value) => json!(value),
+        Label::Number(value) => json!(value),
+    }
+}
+
+// one row per value, in the long format of vega-lite
+fn rows(figure: &Figure, series: &Series) -> Vec<Value> {
+    let xs: Vec<_> = if series.xs.is_empty() {
+        figure.labels.iter().map(label_value).collect()
+    } else {
+        series.xs.iter().map(|x| json!(x)).collect()
+    };
+    let titles = if series.titles.is_empty() {
+        &figure.titles
+    } else {
+        &series.titles
+    };
+    xs.into_iter()
+        .zip(&series.values)
+        .enumerate()
+        .filter_map(|(i, (x, value))| {
+            let value = (*value)?;
+            let title = titles.get(i).cloned().unwrap_or_default();
+            let tooltip = series
+                .tooltips
+                .get(i)
+                .cloned()
+                .unwrap_or_else(|| format!("{}: {}", series.name, value));
+            let mut row = json!({
+                "x": x,
+                "series": series.name,
+                "stack": series.stack,
+                "value": value,
+                "title": title,
+                "tooltip": tooltip,
+            });
+            // the area of the point, in square pixels
+            if let Some(radius) = series.radii.get(i) {
+                row["size"] = json!(std::f64::consts::PI * radius * radius);
+            }
+            Some(row)
+        })
+        .collect()
+}
+
+fn x_encoding(figure: &Figure) -> Value {
+    if figure.temporal {
+        json!({ "field": "x", "type": "temporal", "title": null })
+    } else if figure.labels.is_empty() {
+        json!({ "field": "x", "type": "quantitative", "title": null })
+    } else {
+        json!({ "field": "x", "type": "ordinal", "sort": null, "title": null })
+    }
+}
+
+fn tooltip_encoding() -> Value {
+    json!([
+        { "field": "title", "type": "nominal", "title": " " },
+        { "field": "tooltip", "type": "nominal", "title": " " },
+    ])
+}
+
+// the colors of the series shown in the legend, shared by every layer
+fn color_scale(figure: &Figure) -> Value {
+    let (domain, range): (Vec<_>, Vec<_>) = figure
+        .series
+        .iter()
+        .filter(|series| series.legend && series.mark != Mark::Arc)
+        .map(|series| (series.name.clone(), series.colors[0].clone()))
+        .unzip();
+    json!({ "domain": domain, "range": range })
+}
+
+fn line_layer(figure: &Figure, series: &Series) -> Value {
+    let mut mark = Map::new();
+    mark.insert(
+        "type".into(),
+        json!(if series.mark == Mark::Area {
+            "area"
+        } else {
+            "line"
+        }),
+    );
+    mark.insert("point".into(), json!(series.points));
+    if series.dashed {
+        mark.insert("strokeDash".into(), json!([5, 5]));
+    }
+    if series.mark == Mark::Area {
+        mark.insert("line".into(), json!(true));
+        mark.insert("opacity".into(), json!(1));
+    }
+
+    let mut encoding = json!({
+        "x": x_encoding(figure),
+        "y": { "field": "value", "type": "quantitative", "stack": null, "title": null },
+        "tooltip": tooltip_encoding(),
+    });
+    if series.secondary {
+        encoding["y"]["axis"] = json!({ "orient": "right" });
+    }
+    // the lines joining points share their scale
+    if !series.xs.is_empty() {
+        encoding["y"]["scale"] = json!({ "zero": false });
+    }
+    if series.legend {
+        encoding["color"] = json!({
+            "datum": series.name,
+            "type": "nominal",
+            "scale": color_scale(figure),
+            "title": null,
+        });
+    } else {
+        mark.insert("color".into(), json!(series.colors[0]));
+    }
+
+    json!({
+        "data": { "values": rows(figure, series) },
+        "mark": mark,
+        "encoding": encoding,
+    })
+}
+
+fn point_layer(figure: &Figure, series: &Series) -> Value {
+    let mut encoding = json!({
+        "x": x_encoding(figure),
+        "y": { "field": "value", "type": "quantitative", "scale": { "zero": false }, "title": null },
+        "tooltip": tooltip_encoding(),
+    });
+    if !series.radii.is_empty() {
+        encoding["size"] = json!({ "field": "size", "type": "quantitative", "scale": null });
+    }
+    let mut mark = json!({ "type": "circle", "opacity": 0.5 });
+    if series.legend {
+        encoding["color"] = json!({
+            "datum": series.name,
+            "type": "nominal",
+            "scale": color_scale(figure),
+            "title": null,
+        });
+    } else {
+        mark["color"] = json!(series.colors[0]);
+    }
+
+    json!({
+        "data": { "values": rows(figure, series) },
+        "mark": mark,
+        "encoding": encoding,
+    })
+}
+
+fn bar_layer(figure: &Figure, series: &[&Series]) -> Value {
+    let stacks: Vec<_> = series.iter().filter_map(|x| x.stack.as_ref()).collect();
+    let mut encoding = json!({
+        "x": x_encoding(figure),
+        "y": { "field": "value", "type": "quantitative", "stack": "zero", "title": null },
+        "color": { "field": "series", "type": "nominal", "scale": color_scale(figure), "title": null },
+        "order": { "field": "order" },
+        "tooltip": tooltip_encoding(),
+    });
+    // bars of different stacks are side by side
+    if stacks.iter().any(|stack| *stack != stacks[0]) {
+        encoding["xOffset"] = json!({ "field": "stack" });
+    }
+
+    let values: Vec<_> = series
+        .iter()
+        .enumerate()
+        .flat_map(|(order, series)| {
+            rows(figure, series).into_iter().map(move |mut row| {
+                row["order"] = json!(order);
+                row
+            })
+        })
+        .collect();
+
+    json!({
+        "data": { "values": values },
+        "mark": { "type": "bar" },
+        "encoding": encoding,
+    })
+}
+
+// a pie chart shows the first of its series
+fn arc_spec(figure: &Figure, series: &Series) -> Value {
+    let domain: Vec<_> = figure.labels.iter().map(label_value).collect();
+    json!({
+        "data": { "values": rows(figure, series) },
+        "mark": { "type": "arc", "innerRadius": 50 },
+        "encoding": {
+            "theta": { "field": "value", "type": "quantitative" },
+            "color": {
+                "field": "x",
+                "type": "nominal",
+                "sort": null,
+                "scale": { "domain": domain, "range": series.colors },
+                "title": null,
+            },
+            "tooltip": tooltip_encoding(),
+        },
+    })
+}
+
+/// Vega-Lite (v5) specification of the figure, with the data inlined.
+pub fn vega_lite(figure: &Figure) -> Value {
+    let mut spec = if let Some(series) = figure.series.iter().find(|x| x.mark == Mark::Arc) {
+        arc_spec(figure, series)
+    } else {
+        let bars: Vec<_> = figure
+            .series
+            .iter()
+            .filter(|series| series.mark == Mark::Bar)
+            .collect();
+        // the first series are drawn on top, as in chart.js
+        let lines = |secondary: bool| -> Vec<_> {
+            figure
+                .series
+                .iter()
+                .rev()
+                .filter(|series| series.mark != Mark::Bar)
+                .filter(|series| series.secondary == secondary)
+                .map(|series| match series.mark {
+                    Mark::Point => point_layer(figure, series),
+                    _ => line_layer(figure, series),
+                })
+                .collect()
+        };
+        let mut layers = lines(false);
+        if !bars.is_empty() {
+            layers.insert(0, bar_layer(figure, &bars));
+        }
+        // the series of the second axis share their own scale
+        let secondary = lines(true);
+        if secondary.is_empty() {
+            json!({ "layer": layers })
+        } else {
+            json!({
+                "layer": [{ "layer": layers }, { "layer": secondary }],
+                "resolve": { "scale": { "y": "independent" } },
+            })
+        }
+    };
+
+    spec["$schema"] = json!(SCHEMA);
+    spec["width"] = json!("container");
+    spec["height"] = json!(300);
+    spec
+}
+
+#[cfg(test)]
+mod tests {
+    use super::vega_lite;
+    use crate::chart::dataset::{
+        BarDataset, BarKind, BubbleDataset, BubblePoint, ChartData, Label, LineDataset, PieDataset,
+        Point, PointStyle, ScatterDataset, Tooltip, YAxis,
+    };
+    use crate::chart::figure::{Figure, Mark};
+
+    #[tokio::test]
+    async fn spec() {
+        let chart = ChartData {
+            labels: vec![Label::from(0.0), Label::from(86400000.0)],
+            datasets: vec![
+                LineDataset {
+                    label: Some("Score".into()),
+                    data: vec![Some(500.0), None],
+                    border_color: "#E74C3C".into(),
+                    ..Default::default()
+                },
+                LineDataset {
+                    data: vec![Some(490.0), Some(495.0)],
+                    border_dash: [5.0, 5.0],
+                    point_style: PointStyle::None,
+                    ..Default::default()
+                },
+            ],
+            tooltip: Tooltip {
+                title: vec![vec!["day 0".into(), "day 1".into()]],
+                label: vec![vec!["CRS 500".into(), "".into()]],
+            },
+        };
+        let figure = Figure::from(chart);
+        assert!(figure.temporal);
+        assert_eq!(figure.series[0].mark, Mark::Line);
+        assert!(figure.series[1].dashed && !figure.series[1].legend);
+
+        let spec = vega_lite(&figure);
+        assert_eq!(spec["$schema"], super::SCHEMA);
+        let layers = spec["layer"].as_array().unwrap();
+        assert_eq!(layers.len(), 2);
+        // drawn below the first series
+        assert_eq!(layers[0]["mark"]["strokeDash"], serde_json::json!([5, 5]));
+        assert_eq!(layers[0]["mark"]["color"], "#ffffff");
+        assert_eq!(layers[1]["encoding"]["x"]["type"], "temporal");
+        assert_eq!(
+            layers[1]["encoding"]["color"]["scale"]["domain"][0],
+            "Score"
+        );
+        let values = layers[1]["data"]["values"].as_array().unwrap();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0]["tooltip"], "CRS 500");
+        assert_eq!(values[0]["title"], "day 0");
+
+        let chart = ChartData {
+            labels: vec![Label::from("2024-01"), Label::from("2024-02")],
+            datasets: vec![
+                BarDataset {
+                    label: Some("CEC".into()),
+                    data: vec![Some(1.0), Some(2.0)],
+                    ..Default::default()
+                },
+                BarDataset {
+                    label: Some("FSW".into()),
+                    data: vec![Some(3.0), Some(4.0)],
+                    ..Default::default()
+                },
+            ],
+            tooltip: Tooltip::default(),
+        };
+        let spec = vega_lite(&chart.into());
+        let layers = spec["layer"].as_array().unwrap();
+        assert_eq!(layers.len(), 1);
+        assert_eq!(layers[0]["encoding"]["x"]["type"], "ordinal");
+        assert_eq!(layers[0]["encoding"]["y"]["stack"], "zero");
+        assert!(layers[0]["encoding"].get("xOffset").is_none());
+        assert_eq!(layers[0]["data"]["values"][2]["tooltip"], "FSW: 3");
+
+        let chart = ChartData {
+            labels: vec![Label::from(0.0), Label::from(86400000.0)],
+            datasets: vec![
+                BarDataset {
+                    label: Some("CEC".into()),
+                    data: vec![Some(1.0), Some(2.0)],
+                    ..Default::default()
+                },
+                BarDataset {
+                    label: Some("Year to Date".into()),
+                    data: vec![Some(1.0), Some(3.0)],
+                    kind: BarKind::Line,
+                    y_axis: YAxis::Secondary("ytd".into()),
+                    ..Default::default()
+                },
+            ],
+            tooltip: Tooltip::default(),
+        };
+        let figure = Figure::from(chart);
+        assert!(figure.series[1].secondary && figure.series[1].stack.is_none());
+        let spec = vega_lite(&figure);
+        assert_eq!(spec["resolve"]["scale"]["y"], "independent");
+        let line = &spec["layer"][1]["layer"][0];
+        assert_eq!(line["mark"]["type"], "line");
+        assert_eq!(line["encoding"]["y"]["axis"]["orient"], "right");
+
+        let chart = ChartData {
+            labels: vec![Label::from("Used"), Label::from("Unused")],
+            datasets: vec![PieDataset {
+                data: vec![1.0, 3.0],
+                background_color: vec!["#58D68D".into(), "#F4D03F".into()],
+                ..Default::default()
+            }],
+            tooltip: Tooltip::default(),
+        };
+        let spec = vega_lite(&chart.into());
+        assert_eq!(spec["mark"]["type"], "arc");
+        assert_eq!(spec["encoding"]["color"]["scale"]["range"][1], "#F4D03F");
+
+        let point = |x, y| Point { x, y };
+        let chart = ChartData {
+            labels: Vec::new(),
+            datasets: vec![
+                ScatterDataset {
+                    label: Some("General".into()),
+                    data: vec![point(1000.0, 520.0), point(3000.0, 500.0)],
+                    border_color: "#E74C3C".into(),
+                    ..Default::default()
+                },
+                ScatterDataset {
+                    data: vec![point(1000.0, 521.0), point(3000.0, 499.0)],
+                    show_line: true,
+                    point_style: PointStyle::None,
+                    ..Default::default()
+                },
+            ],
+            tooltip: Tooltip {
+                title: vec![vec!["2024-01-10".into(), "2024-01-24".into()]],
+                label: Vec::new(),
+            },
+        };
+        let figure = Figure::from(chart);
+        assert!(!figure.temporal);
+        assert_eq!(figure.series[0].mark, Mark::Point);
+        assert_eq!(figure.series[1].mark, Mark::Line);
+        let spec = vega_lite(&figure);
+        let layers = spec["layer"].as_array().unwrap();
+        assert_eq!(layers[0]["mark"]["type"], "line");
+        assert_eq!(layers[1]["mark"]["type"], "circle");
+        assert_eq!(layers[1]["encoding"]["x"]["type"], "quantitative");
+        let values = layers[1]["data"]["values"].as_array().unwrap();
+        assert_eq!(values[1]["x"], 3000.0);
+        assert_eq!(values[1]["title"], "2024-01-24");
+        assert!(values[1].get("size").is_none());
+
+        let chart = ChartData {
+            labels: Vec::new(),
+            datasets: vec![BubbleDataset {
+                label: Some("General".into()),
+                data: vec![BubblePoint {
+                    x: 0.0,
+                    y: 520.0,
+                    r: 2.0,
+                }],
+                ..Default::default()
+            }],
+            tooltip: Tooltip::default(),
+        };
+        let spec = vega_lite(&chart.into());
+        let layer = &spec["layer"][0];
+        assert_eq!(layer["encoding"]["x"]["type"], "temporal");
+        assert_eq!(layer["encoding"]["size"]["scale"], serde_json::Value::Null);
+        let size = layer["data"]["values"][0]["size"].as_f64().unwrap();
+        assert!((size - 4.0 * std::f64::consts::PI).abs() < 1e-9);
+    }
+}
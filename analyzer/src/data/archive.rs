@@ -0,0 +1,158 @@
+use super::diff::SnapshotDiff;
+use super::raw::EeRounds123En;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const TIME_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotMeta {
+    pub fetched_at: DateTime<Utc>,
+    pub hash: String,
+    pub path: PathBuf,
+}
+
+impl SnapshotMeta {
+    fn parse(path: &Path) -> Option<Self> {
+        let (time, hash) = path.file_stem()?.to_str()?.split_once('-')?;
+        let fetched_at = NaiveDateTime::parse_from_str(time, TIME_FORMAT)
+            .ok()?
+            .and_utc();
+        Some(Self {
+            fetched_at,
+            hash: hash.into(),
+            path: path.into(),
+        })
+    }
+}
+
+/// Local store of every distinct fetched snapshot of the IRCC data, one json file per
+/// snapshot named after the fetch time and the content hash.
+pub struct Archive {
+    dir: PathBuf,
+}
+
+impl Archive {
+    pub fn open(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    pub fn hash(raw_data: &EeRounds123En) -> String {
+        let content = serde_json::to_vec(raw_data).unwrap();
+        Sha256::digest(content)
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    // ensure sorted
+    pub fn snapshots(&self) -> io::Result<Vec<SnapshotMeta>> {
+        let mut snapshots = Vec::new();
+        for entry in fs::read_dir(&self.dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                snapshots.extend(SnapshotMeta::parse(&path));
+            }
+        }
+        snapshots.sort_by_key(|snapshot| snapshot.fetched_at);
+        Ok(snapshots)
+    }
+
+    pub fn load(&self, snapshot: &SnapshotMeta) -> io::Result<EeRounds123En> {
+        let content = fs::read(&snapshot.path)?;
+        serde_json::from_slice(&content).map_err(io::Error::other)
+    }
+
+    pub fn latest(&self) -> io::Result<Option<EeRounds123En>> {
+        match self.snapshots()?.last() {
+            Some(snapshot) => self.load(snapshot).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// stores the snapshot unless it is identical to the latest one.
+    pub fn store(
+        &self,
+        raw_data: &EeRounds123En,
+        fetched_at: DateTime<Utc>,
+    ) -> io::Result<Option<SnapshotMeta>> {
+        let hash = Self::hash(raw_data);
+        if self
+            .snapshots()?
+            .last()
+            .is_some_and(|snapshot| snapshot.hash == hash)
+        {
+            return Ok(None);
+        }
+
+        let path = self
+            .dir
+            .join(format!("{}-{}.json", fetched_at.format(TIME_FORMAT), hash));
+        fs::write(&path, serde_json::to_vec(raw_data).unwrap())?;
+        Ok(SnapshotMeta::parse(&path))
+    }
+
+    /// differences between each pair of consecutive snapshots.
+    pub fn changelog(&self) -> io::Result<Vec<SnapshotDiff>> {
+        let snapshots = self.snapshots()?;
+        let mut changelog = Vec::with_capacity(snapshots.len());
+
+        let mut previous: Option<EeRounds123En> = None;
+        for snapshot in &snapshots {
+            let current = self.load(snapshot)?;
+            if let Some(previous) = &previous {
+                changelog.push(SnapshotDiff::diff(
+                    previous,
+                    &current,
+                    snapshot.fetched_at.timestamp_millis(),
+                ));
+            }
+            previous = Some(current);
+        }
+        Ok(changelog)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::raw::EeRounds123En;
+    use super::Archive;
+    use chrono::{TimeZone, Utc};
+
+    #[tokio::test]
+    async fn store() {
+        let dir = std::env::temp_dir().join(format!("analyzer-archive-{}", std::process::id()));
+        let archive = Archive::open(&dir).unwrap();
+
+        let data0 = EeRounds123En {
+            classes: "0".into(),
+            rounds: Vec::new(),
+        };
+        let data1 = EeRounds123En {
+            classes: "1".into(),
+            rounds: Vec::new(),
+        };
+        let time = |day| Utc.with_ymd_and_hms(2024, 1, day, 12, 0, 0).unwrap();
+
+        assert!(archive.store(&data0, time(1)).unwrap().is_some());
+        assert!(archive.store(&data0, time(2)).unwrap().is_none());
+        assert!(archive.store(&data1, time(3)).unwrap().is_some());
+
+        let snapshots = archive.snapshots().unwrap();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].fetched_at, time(1));
+        assert_eq!(snapshots[1].hash, Archive::hash(&data1));
+        assert_eq!(archive.latest().unwrap().unwrap().classes, "1");
+
+        let changelog = archive.changelog().unwrap();
+        assert_eq!(changelog.len(), 1);
+        assert_eq!(changelog[0].timestamp, time(3).timestamp_millis());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,207 @@
+use super::raw::{EeRounds123En, RawData};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    pub field: String,
+    pub old: String,
+    pub new: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DrawChange {
+    pub draw_number: String,
+    pub fields: Vec<FieldChange>,
+}
+
+/// Differences between two fetched snapshots of the IRCC data.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotDiff {
+    /// timestamp (milliseconds) of the newer snapshot.
+    pub timestamp: i64,
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    pub modified: Vec<DrawChange>,
+    /// `drawDistributionAsOn` of the pool distributions published since the older snapshot.
+    pub new_pools: Vec<String>,
+}
+
+impl SnapshotDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.modified.is_empty()
+            && self.new_pools.is_empty()
+    }
+
+    pub fn diff(old: &EeRounds123En, new: &EeRounds123En, timestamp: i64) -> Self {
+        let old_rounds = Self::rounds(old);
+        let new_rounds = Self::rounds(new);
+
+        let added = new_rounds
+            .keys()
+            .filter(|id| !old_rounds.contains_key(*id))
+            .cloned()
+            .collect();
+        let removed = old_rounds
+            .keys()
+            .filter(|id| !new_rounds.contains_key(*id))
+            .cloned()
+            .collect();
+
+        let modified = new_rounds
+            .iter()
+            .filter_map(|(id, new_round)| {
+                let old_round = old_rounds.get(id)?;
+                let fields: Vec<_> = new_round
+                    .iter()
+                    .filter(|(field, value)| old_round.get(*field) != Some(*value))
+                    .map(|(field, value)| FieldChange {
+                        field: field.clone(),
+                        old: old_round.get(field).cloned().unwrap_or_default(),
+                        new: value.clone(),
+                    })
+                    .collect();
+
+                if fields.is_empty() {
+                    None
+                } else {
+                    Some(DrawChange {
+                        draw_number: id.clone(),
+                        fields,
+                    })
+                }
+            })
+            .collect();
+
+        let old_pools = Self::pools(old);
+        let new_pools = Self::pools(new).difference(&old_pools).cloned().collect();
+
+        Self {
+            timestamp,
+            added,
+            removed,
+            modified,
+            new_pools,
+        }
+    }
+
+    // fields of each round by draw number
+    fn rounds(raw_data: &EeRounds123En) -> BTreeMap<String, BTreeMap<String, String>> {
+        raw_data
+            .rounds
+            .iter()
+            .map(|round| {
+                let fields = match serde_json::to_value(round).unwrap() {
+                    serde_json::Value::Object(map) => map
+                        .into_iter()
+                        .map(|(field, value)| match value {
+                            serde_json::Value::String(value) => (field, value),
+                            value => (field, value.to_string()),
+                        })
+                        .collect(),
+                    _ => BTreeMap::new(),
+                };
+                (round.draw_number.clone(), fields)
+            })
+            .collect()
+    }
+
+    // dates of the published pool distributions
+    fn pools(raw_data: &EeRounds123En) -> BTreeSet<String> {
+        fn is_published(round: &RawData) -> bool {
+            [
+                &round.dd1,
+                &round.dd2,
+                &round.dd4,
+                &round.dd5,
+                &round.dd6,
+                &round.dd7,
+                &round.dd8,
+                &round.dd10,
+                &round.dd11,
+                &round.dd12,
+                &round.dd13,
+                &round.dd14,
+                &round.dd15,
+                &round.dd16,
+                &round.dd17,
+            ]
+            .iter()
+            .any(|count| count.replace(',', "").parse::<i64>().unwrap_or(0) != 0)
+        }
+
+        raw_data
+            .rounds
+            .iter()
+            .filter(|round| is_published(round))
+            .map(|round| round.draw_distribution_as_on.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::raw::{EeRounds123En, RawData};
+    use super::SnapshotDiff;
+
+    fn round(draw_number: &str, size: &str, as_on: &str, dd17: &str) -> RawData {
+        serde_json::from_value(serde_json::json!({
+            "DrawText1": "", "dd1": "0", "dd2": "0", "dd3": "0", "dd4": "0", "dd5": "0",
+            "dd6": "0", "dd7": "0", "dd8": "0", "dd9": "0", "dd10": "0", "dd11": "0",
+            "dd12": "0", "dd13": "0", "dd14": "0", "dd15": "0", "dd16": "0", "dd17": dd17,
+            "dd18": "0", "drawCRS": "500", "drawCutOff": "", "drawDate": "",
+            "drawDateFull": "", "drawDateTime": "", "drawDistributionAsOn": as_on,
+            "drawName": "General", "drawNumber": draw_number, "drawNumberURL": "",
+            "drawSize": size, "drawText2": "", "mitext": "",
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn diff() {
+        let old = EeRounds123En {
+            classes: "".into(),
+            rounds: vec![
+                round("1", "1,000", "January 1, 2024", "0"),
+                round("2", "2,000", "January 15, 2024", "0"),
+            ],
+        };
+        let new = EeRounds123En {
+            classes: "".into(),
+            rounds: vec![
+                round("2", "2,500", "January 15, 2024", "1,234"),
+                round("3", "3,000", "January 29, 2024", "0"),
+            ],
+        };
+
+        let diff = SnapshotDiff::diff(&old, &new, 0);
+        assert_eq!(diff.added, vec!["3".to_string()]);
+        assert_eq!(diff.removed, vec!["1".to_string()]);
+        assert_eq!(diff.modified.len(), 1);
+        assert_eq!(diff.modified[0].draw_number, "2");
+
+        let fields: Vec<_> = diff.modified[0]
+            .fields
+            .iter()
+            .map(|change| {
+                (
+                    change.field.as_str(),
+                    change.old.as_str(),
+                    change.new.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            fields,
+            vec![("dd17", "0", "1,234"), ("drawSize", "2,000", "2,500")]
+        );
+        assert_eq!(diff.new_pools, vec!["January 15, 2024".to_string()]);
+
+        assert!(SnapshotDiff::diff(&new, &new, 0).is_empty());
+    }
+}
@@ -0,0 +1,193 @@
+use super::{DateRange, Invite};
+use chrono::{Datelike, Days, Months, NaiveDate, Weekday};
+use std::ops::Range;
+
+/// How the invitations are bucketed by date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Granularity {
+    Day,
+    Week,
+    Month,
+    Quarter,
+    Year,
+    /// the fiscal year of IRCC, from April to March.
+    FiscalYear,
+    /// the invitations of the given number of days up to each draw.
+    Rolling(u32),
+}
+
+/// A bucket of invitations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Period {
+    /// where the bucket is placed on the time axis.
+    pub date: NaiveDate,
+    /// first and last days of the bucket, both inclusive.
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// indices of the invitations of the bucket.
+    pub invites: Range<usize>,
+}
+
+impl Granularity {
+    /// "d", "w", "m", "q", "y", "fy" or "r" followed by a number of days (e.g. "r30").
+    pub fn parse(x: &str) -> Option<Self> {
+        match x {
+            "d" => Some(Self::Day),
+            "w" => Some(Self::Week),
+            "m" => Some(Self::Month),
+            "q" => Some(Self::Quarter),
+            "y" => Some(Self::Year),
+            "fy" => Some(Self::FiscalYear),
+            _ => x
+                .strip_prefix('r')
+                .and_then(|days| days.parse().ok())
+                .filter(|days| *days > 0)
+                .map(Self::Rolling),
+        }
+    }
+
+    /// first day of the period containing the date, the draws of a day for rolling windows.
+    fn start(self, date: NaiveDate) -> NaiveDate {
+        let first_of = |year, month| NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+        match self {
+            Self::Day | Self::Rolling(_) => date,
+            Self::Week => date.week(Weekday::Mon).first_day(),
+            Self::Month => first_of(date.year(), date.month()),
+            Self::Quarter => first_of(date.year(), date.month0() / 3 * 3 + 1),
+            Self::Year => first_of(date.year(), 1),
+            Self::FiscalYear if date.month() >= 4 => first_of(date.year(), 4),
+            Self::FiscalYear => first_of(date.year() - 1, 4),
+        }
+    }
+
+    /// last day of the period starting at `start`.
+    fn end(self, start: NaiveDate) -> NaiveDate {
+        let months = |n| start + Months::new(n) - Days::new(1);
+        match self {
+            Self::Day | Self::Rolling(_) => start,
+            Self::Week => start + Days::new(6),
+            Self::Month => months(1),
+            Self::Quarter => months(3),
+            Self::Year | Self::FiscalYear => months(12),
+        }
+    }
+
+    /// the buckets of the invitations within the range, the indices are of `invite_data`.
+    /// a rolling window also counts the invitations before the range.
+    pub fn periods(self, invite_data: &[Invite], range: DateRange) -> Vec<Period> {
+        let (first, last) = range.bounds(invite_data, |invite| invite.date);
+
+        let mut periods: Vec<Period> = Vec::new();
+        for (i, invite) in invite_data.iter().enumerate().take(last).skip(first) {
+            match periods.last_mut() {
+                Some(period) if invite.date <= period.end => period.invites.end = i + 1,
+                _ => {
+                    let start = self.start(invite.date);
+                    periods.push(Period {
+                        date: start,
+                        start,
+                        end: self.end(start),
+                        invites: i..i + 1,
+                    });
+                }
+            }
+        }
+
+        // the windows end at the draws instead, those reaching before the calendar start at the
+        // first draw
+        if let Self::Rolling(days) = self {
+            for period in periods.iter_mut() {
+                period.date = invite_data[period.invites.start].date;
+                period.end = period.date;
+                period.start = period
+                    .date
+                    .checked_sub_days(Days::new(days as u64 - 1))
+                    .unwrap_or(invite_data[0].date);
+                period.invites.start = invite_data.partition_point(|x| x.date < period.start);
+                period.invites.end = invite_data.partition_point(|x| x.date <= period.end);
+            }
+        }
+        periods
+    }
+
+    /// the dates of the period in the tooltips.
+    pub fn describe(self, period: &Period) -> String {
+        let dates = format!(
+            "{} - {}",
+            period.start.format("%Y-%m-%d"),
+            period.end.format("%Y-%m-%d")
+        );
+        match self {
+            Self::Day => format!("{}", period.start.format("%Y-%m-%d")),
+            Self::Quarter => format!(
+                "{} Q{} ({})",
+                period.start.year(),
+                period.start.month0() / 3 + 1,
+                dates
+            ),
+            Self::Year => format!("{} ({})", period.start.year(), dates),
+            Self::FiscalYear => format!(
+                "FY {}-{:02} ({})",
+                period.start.year(),
+                (period.start.year() + 1) % 100,
+                dates
+            ),
+            Self::Week | Self::Month | Self::Rolling(_) => dates,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Granularity;
+    use crate::data::test_util::invites;
+    use crate::data::DateRange;
+    use chrono::NaiveDate;
+
+    #[tokio::test]
+    async fn granularity() {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).unwrap();
+        let invite_data = invites(&[
+            (date(2024, 3, 20), "General", 1000.0, 500.0),
+            (date(2024, 4, 2), "General", 1000.0, 500.0),
+            (date(2024, 4, 16), "General", 1000.0, 500.0),
+            (date(2025, 1, 8), "General", 1000.0, 500.0),
+        ]);
+
+        assert_eq!(Granularity::parse("r30"), Some(Granularity::Rolling(30)));
+        assert_eq!(Granularity::parse("r0"), None);
+        assert_eq!(Granularity::parse("x"), None);
+
+        let periods = Granularity::Quarter.periods(&invite_data, DateRange::ALL);
+        let indices: Vec<_> = periods.iter().map(|x| x.invites.clone()).collect();
+        assert_eq!(indices, vec![0..1, 1..3, 3..4]);
+        assert_eq!(
+            Granularity::Quarter.describe(&periods[1]),
+            "2024 Q2 (2024-04-01 - 2024-06-30)"
+        );
+
+        let periods = Granularity::FiscalYear.periods(&invite_data, DateRange::ALL);
+        let indices: Vec<_> = periods.iter().map(|x| x.invites.clone()).collect();
+        assert_eq!(indices, vec![0..1, 1..4]);
+        assert_eq!(
+            Granularity::FiscalYear.describe(&periods[1]),
+            "FY 2024-25 (2024-04-01 - 2025-03-31)"
+        );
+
+        // the first window reaches before the range
+        let range = DateRange::new(Some(date(2024, 4, 1)), None);
+        let periods = Granularity::Rolling(30).periods(&invite_data, range);
+        let indices: Vec<_> = periods.iter().map(|x| x.invites.clone()).collect();
+        assert_eq!(indices, vec![0..2, 0..3, 3..4]);
+        assert_eq!(periods[1].date, date(2024, 4, 16));
+        assert_eq!(
+            Granularity::Rolling(30).describe(&periods[1]),
+            "2024-03-18 - 2024-04-16"
+        );
+        let periods = Granularity::parse("r4000000000")
+            .unwrap()
+            .periods(&invite_data, DateRange::ALL);
+        assert_eq!(periods[3].invites, 0..4);
+        assert_eq!(periods[3].start, date(2024, 3, 20));
+    }
+}
@@ -1,11 +1,11 @@
 use super::raw::{EeRounds123En, RawData};
-use super::utils::{parse_date, parse_i32};
-use super::{Category, Pathway};
-use chrono::NaiveDate;
+use super::utils::{parse_date, parse_i32, parse_tie_break};
+use super::{Category, CategoryCode, Pathway, Program};
+use chrono::{NaiveDate, NaiveDateTime};
 use itertools::Itertools;
 use std::cmp::Ordering;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct InviteId(i32, i32);
 
 impl std::fmt::Display for InviteId {
@@ -19,6 +19,15 @@ impl std::fmt::Display for InviteId {
 }
 
 impl InviteId {
+    /// `part` tells apart the draws sharing the same number, e.g. 91a and 91b.
+    pub fn new(number: i32, part: i32) -> Self {
+        Self(number, part)
+    }
+
+    pub fn part(&self) -> i32 {
+        self.1
+    }
+
     pub fn parse(x: &str) -> Self {
         match x {
             "91a" => Self(91, 0),
@@ -42,6 +51,8 @@ pub struct Invite {
     pub pathway: Pathway,
     pub size: f64,
     pub score: f64,
+    /// candidates at the cutoff score who submitted their profile after it were not invited.
+    pub tie_break: Option<NaiveDateTime>,
 }
 
 impl PartialEq for Invite {
@@ -73,6 +84,7 @@ impl Invite {
             pathway: Pathway::parse(&raw_data.draw_text2),
             size: parse_i32(&raw_data.draw_size) as f64,
             score: parse_i32(&raw_data.draw_crs) as f64,
+            tie_break: parse_tie_break(&raw_data.draw_cutoff),
         }
     }
 
@@ -90,6 +102,26 @@ impl Invite {
     pub fn is_valid(&self) -> bool {
         self.category.is_valid() && self.pathway.is_valid()
     }
+
+    /// the program the draw invited from.
+    pub fn program(&self) -> Program {
+        Program::of(self.category, self.pathway)
+    }
+
+    /// the occupational category of the draw, none for the draws open to every occupation or
+    /// restricted to a program.
+    pub fn occupation(&self) -> Option<CategoryCode> {
+        let code = self.category.code;
+        let occupational = matches!(
+            code,
+            CategoryCode::Stem
+                | CategoryCode::Health
+                | CategoryCode::Trade
+                | CategoryCode::Transport
+                | CategoryCode::Agriculture
+        );
+        (occupational && self.program() == Program::Mixed).then_some(code)
+    }
 }
 
 #[cfg(test)]
@@ -1,19 +1,45 @@
+#[cfg(feature = "archive")]
+mod archive;
 mod category;
+mod diff;
+mod granularity;
 mod invite;
 mod pathway;
 mod pool;
+mod program;
+mod provincial;
 mod raw;
 mod plan;
+mod range;
+mod source;
+#[cfg(feature = "sqlite")]
+mod store;
+#[cfg(test)]
+pub(crate) mod test_util;
 
 use async_once_cell::OnceCell;
-use raw::raw_data;
 use wasm_bindgen::prelude::wasm_bindgen;
 
+#[cfg(feature = "archive")]
+pub use archive::{Archive, SnapshotMeta};
 pub use category::{Category, CategoryCode};
+pub use diff::{DrawChange, FieldChange, SnapshotDiff};
+pub use granularity::{Granularity, Period};
 pub use invite::{Invite, InviteId};
 pub use pathway::Pathway;
 pub use pool::Pool;
-pub use plan::Plan;
+pub use program::Program;
+pub use provincial::{Province, ProvincialDraw};
+pub use plan::{all_plan, Plan};
+pub use range::DateRange;
+pub use raw::{raw_data, EeRounds123En, RawData, IRCC_URL};
+pub use source::{
+    source_from_arg, AnalysisData, ArgSource, DataSource, DrawSource, FileSource, HttpSource,
+};
+#[cfg(feature = "sqlite")]
+pub use source::SqliteSource;
+#[cfg(feature = "sqlite")]
+pub use store::SqliteStore;
 
 #[wasm_bindgen]
 pub async fn wasm_invite_data() -> *const Vec<Invite> {
@@ -40,13 +66,18 @@ pub async fn wasm_plan_data() -> *const Vec<Plan> {
 }
 
 mod utils {
-    use chrono::NaiveDate;
+    use chrono::{NaiveDate, NaiveDateTime};
     use wasm_bindgen::UnwrapThrowExt;
 
     pub fn parse_date(x: &str) -> NaiveDate {
         NaiveDate::parse_from_str(x, "%B %d, %Y").unwrap_throw()
     }
 
+    // e.g. "January 10, 2024 at 15:43:29 UTC", absent for the earliest draws
+    pub fn parse_tie_break(x: &str) -> Option<NaiveDateTime> {
+        NaiveDateTime::parse_from_str(x, "%B %d, %Y at %H:%M:%S UTC").ok()
+    }
+
     pub fn parse_i32(x: &str) -> i32 {
         x.replace(",", "").parse().unwrap_throw()
     }
@@ -7,6 +7,23 @@ impl Pathway {
     const FSW: u32 = 0x0100;
     const FST: u32 = 0x1000;
 
+    pub fn new(pnp: bool, cec: bool, fsw: bool, fst: bool) -> Self {
+        let mut x = 0;
+        if pnp {
+            x |= Pathway::PNP;
+        }
+        if cec {
+            x |= Pathway::CEC;
+        }
+        if fsw {
+            x |= Pathway::FSW;
+        }
+        if fst {
+            x |= Pathway::FST;
+        }
+        Self(x)
+    }
+
     pub fn is_pnp(&self) -> bool {
         (self.0 & Pathway::PNP) != 0
     }
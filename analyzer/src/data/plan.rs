@@ -1,16 +1,37 @@
+use super::CategoryCode;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Plan {
     pub year: i32,
     pub min: f64,
     pub max: f64,
+    /// the invitations the year aims at per category, indexed by `CategoryCode`.
+    pub targets: [Option<f64>; CategoryCode::N],
 }
 
 impl Plan {
     pub fn new(year: i32, min: f64, max: f64) -> Self {
-        Self { year, min, max }
+        Self {
+            year,
+            min,
+            max,
+            targets: [None; CategoryCode::N],
+        }
+    }
+
+    pub fn with_target(mut self, category: CategoryCode, target: f64) -> Self {
+        self.targets[category as usize] = Some(target);
+        self
+    }
+
+    pub fn target(&self, category: CategoryCode) -> Option<f64> {
+        self.targets.get(category as usize).copied().flatten()
     }
 }
 
+/// the admission ranges of the Federal High Skilled programs of the levels plans. The 2025-2027
+/// plan splits them into the In-Canada Focus, i.e. the CEC draws, and the Federal Economic
+/// Priorities, which are shared by the occupation and French categories without a target each.
 pub async fn all_plan() -> Vec<Plan> {
     vec![
         Plan::new(2015, 68000.0, 74000.0),
@@ -23,7 +44,25 @@ pub async fn all_plan() -> Vec<Plan> {
         Plan::new(2022, 52000.0, 64000.0),
         Plan::new(2023, 67750.0, 88000.0),
         Plan::new(2024, 90000.0, 116000.0),
-        Plan::new(2025, 96500.0, 124000.0),
+        Plan::new(2025, 96500.0, 124000.0).with_target(CategoryCode::Inland, 82980.0),
         Plan::new(2026, 96500.0, 124000.0),
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::all_plan;
+    use crate::data::CategoryCode;
+
+    #[tokio::test]
+    async fn targets() {
+        let plan_data = all_plan().await;
+        let plan = plan_data.iter().find(|plan| plan.year == 2025).unwrap();
+        assert_eq!(plan.target(CategoryCode::Inland), Some(82980.0));
+        assert_eq!(plan.target(CategoryCode::French), None);
+        assert!(plan_data
+            .iter()
+            .filter(|plan| plan.year != 2025)
+            .all(|plan| plan.targets.iter().all(Option::is_none)));
+    }
+}
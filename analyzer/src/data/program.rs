@@ -0,0 +1,113 @@
+use super::{Category, CategoryCode, Pathway};
+use serde::Serialize;
+
+/// The program a draw invited from, `Mixed` when several programs are eligible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum Program {
+    Mixed = 0,
+    Cec,
+    Fsw,
+    Fst,
+    Pnp,
+}
+
+impl Program {
+    pub const N: usize = 5;
+
+    pub fn values() -> &'static [Program] {
+        &[Self::Mixed, Self::Cec, Self::Fsw, Self::Fst, Self::Pnp]
+    }
+
+    /// the program of a draw, told by its name first since the pathway of some draws lists
+    /// every program, e.g. the first trade draws were named "Federal Skilled Trades" but the
+    /// trade occupation draws carry a category year.
+    pub fn of(category: Category, pathway: Pathway) -> Self {
+        match category.code {
+            CategoryCode::Inland => return Self::Cec,
+            CategoryCode::Oversea => return Self::Fsw,
+            CategoryCode::Province => return Self::Pnp,
+            CategoryCode::Trade if category.year.is_none() => return Self::Fst,
+            _ => {}
+        }
+
+        match (
+            pathway.is_cec(),
+            pathway.is_fsw(),
+            pathway.is_fst(),
+            pathway.is_pnp(),
+        ) {
+            (true, false, false, false) => Self::Cec,
+            (false, true, false, false) => Self::Fsw,
+            (false, false, true, false) => Self::Fst,
+            (false, false, false, true) => Self::Pnp,
+            _ => Self::Mixed,
+        }
+    }
+
+    /// the category the draws of the program are counted under.
+    pub fn category(&self) -> Option<CategoryCode> {
+        match self {
+            Self::Mixed => None,
+            Self::Cec => Some(CategoryCode::Inland),
+            Self::Fsw => Some(CategoryCode::Oversea),
+            Self::Fst => Some(CategoryCode::Trade),
+            Self::Pnp => Some(CategoryCode::Province),
+        }
+    }
+
+    pub fn as_str(&self) -> String {
+        match self {
+            Self::Mixed => "Mixed".into(),
+            Self::Cec => "CEC".into(),
+            Self::Fsw => "FSW".into(),
+            Self::Fst => "FST".into(),
+            Self::Pnp => "PNP".into(),
+        }
+    }
+
+    pub fn as_color(&self) -> String {
+        match self {
+            Self::Mixed => "#ECF0F1".into(),
+            Self::Cec => "#E74C3C".into(),
+            Self::Fsw => "#C0392B".into(),
+            Self::Fst => "#7F8C8D".into(),
+            Self::Pnp => "#9B59B6".into(),
+        }
+    }
+}
+
+impl Serialize for Program {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str().as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Program;
+    use crate::data::{Category, CategoryCode, Pathway};
+
+    #[tokio::test]
+    async fn classify() {
+        let every = "Federal Skilled Worker, Canadian Experience Class, Federal Skilled Trades \
+                     and Provincial Nominee Program";
+        let of =
+            |name: &str, pathway: &str| Program::of(Category::parse(name), Pathway::parse(pathway));
+
+        // the name tells the program of the draws listing every program
+        assert_eq!(of("Canadian Experience Class", every), Program::Cec);
+        assert_eq!(of("Federal Skilled Trades", every), Program::Fst);
+        assert_eq!(of("Trade occupations (2023-1)", every), Program::Mixed);
+        assert_eq!(of("General", every), Program::Mixed);
+        assert_eq!(
+            of("No Program Specified", "Provincial Nominee Program"),
+            Program::Pnp
+        );
+        assert_eq!(Program::Cec.category(), Some(CategoryCode::Inland));
+        assert_eq!(Program::Mixed.category(), None);
+    }
+}
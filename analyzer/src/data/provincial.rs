@@ -0,0 +1,243 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Province {
+    Alberta,
+    BritishColumbia,
+    Manitoba,
+    NewBrunswick,
+    Newfoundland,
+    NovaScotia,
+    NorthwestTerritories,
+    Ontario,
+    PrinceEdwardIsland,
+    Saskatchewan,
+    Yukon,
+}
+
+impl Province {
+    pub fn values() -> &'static [Province] {
+        &[
+            Self::Alberta,
+            Self::BritishColumbia,
+            Self::Manitoba,
+            Self::NewBrunswick,
+            Self::Newfoundland,
+            Self::NovaScotia,
+            Self::NorthwestTerritories,
+            Self::Ontario,
+            Self::PrinceEdwardIsland,
+            Self::Saskatchewan,
+            Self::Yukon,
+        ]
+    }
+
+    /// the postal abbreviation, e.g. "ON", or the name of the province.
+    pub fn parse(x: &str) -> Option<Self> {
+        let x = x.trim();
+        Self::values()
+            .iter()
+            .find(|province| {
+                province.as_str().eq_ignore_ascii_case(x) || province.name().eq_ignore_ascii_case(x)
+            })
+            .copied()
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Alberta => "AB",
+            Self::BritishColumbia => "BC",
+            Self::Manitoba => "MB",
+            Self::NewBrunswick => "NB",
+            Self::Newfoundland => "NL",
+            Self::NovaScotia => "NS",
+            Self::NorthwestTerritories => "NT",
+            Self::Ontario => "ON",
+            Self::PrinceEdwardIsland => "PE",
+            Self::Saskatchewan => "SK",
+            Self::Yukon => "YT",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Alberta => "Alberta",
+            Self::BritishColumbia => "British Columbia",
+            Self::Manitoba => "Manitoba",
+            Self::NewBrunswick => "New Brunswick",
+            Self::Newfoundland => "Newfoundland and Labrador",
+            Self::NovaScotia => "Nova Scotia",
+            Self::NorthwestTerritories => "Northwest Territories",
+            Self::Ontario => "Ontario",
+            Self::PrinceEdwardIsland => "Prince Edward Island",
+            Self::Saskatchewan => "Saskatchewan",
+            Self::Yukon => "Yukon",
+        }
+    }
+
+    pub fn as_color(&self) -> String {
+        match self {
+            Self::Alberta => "#3498DB".into(),
+            Self::BritishColumbia => "#16A085".into(),
+            Self::Manitoba => "#F39C12".into(),
+            Self::NewBrunswick => "#C0392B".into(),
+            Self::Newfoundland => "#8E44AD".into(),
+            Self::NovaScotia => "#2980B9".into(),
+            Self::NorthwestTerritories => "#7F8C8D".into(),
+            Self::Ontario => "#E74C3C".into(),
+            Self::PrinceEdwardIsland => "#2ECC71".into(),
+            Self::Saskatchewan => "#F1C40F".into(),
+            Self::Yukon => "#D35400".into(),
+        }
+    }
+}
+
+impl Serialize for Province {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Province {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let x = String::deserialize(deserializer)?;
+        Self::parse(&x).ok_or_else(|| serde::de::Error::custom(format!("unknown province {}", x)))
+    }
+}
+
+/// A draw of an Express Entry stream of a provincial nominee program, the candidates it invites
+/// get 600 points once nominated.
+///
+/// As json, a list of `{"province": "ON", "date": "2024-03-05", "size": 1200, "minScore": 460,
+/// "occupations": ["21231"]}`, or as csv with the header `province,date,size,min_score,occupations`
+/// and the occupations separated by `;`. The minimum score and the occupations may be left out.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProvincialDraw {
+    pub province: Province,
+    pub date: NaiveDate,
+    pub size: f64,
+    /// minimum CRS score of the invited candidates.
+    #[serde(default)]
+    pub min_score: Option<f64>,
+    /// NOC codes the draw was restricted to, none for any occupation.
+    #[serde(default)]
+    pub occupations: Vec<String>,
+}
+
+impl ProvincialDraw {
+    // ensure sorted
+    fn sorted(mut draws: Vec<Self>) -> Vec<Self> {
+        draws.sort_by_key(|draw| (draw.date, draw.province));
+        draws
+    }
+
+    pub fn parse_json(x: &str) -> Result<Vec<Self>, String> {
+        serde_json::from_str(x)
+            .map(Self::sorted)
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn parse_csv(x: &str) -> Result<Vec<Self>, String> {
+        // numbered before the blank lines are skipped, as in the file
+        let mut lines = x
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+        let header: Vec<_> = lines
+            .next()
+            .ok_or("missing header")?
+            .1
+            .split(',')
+            .map(|column| column.trim().to_lowercase())
+            .collect();
+        let column = |name: &str| {
+            header
+                .iter()
+                .position(|column| column == name)
+                .ok_or_else(|| format!("missing column {}", name))
+        };
+        let (province, date, size) = (column("province")?, column("date")?, column("size")?);
+        let (min_score, occupations) = (column("min_score").ok(), column("occupations").ok());
+
+        let draws = lines
+            .map(|(i, line)| {
+                let fields: Vec<_> = line.split(',').map(|field| field.trim()).collect();
+                let field = |i: usize| fields.get(i).copied().unwrap_or_default();
+                let optional = |i: Option<usize>| i.map(field).filter(|x| !x.is_empty());
+                let error = |e: String| format!("line {}: {}", i + 1, e);
+
+                Ok(Self {
+                    province: Province::parse(field(province))
+                        .ok_or_else(|| error(format!("unknown province {}", field(province))))?,
+                    date: NaiveDate::parse_from_str(field(date), "%Y-%m-%d")
+                        .map_err(|e| error(e.to_string()))?,
+                    size: field(size)
+                        .parse()
+                        .map_err(|e: std::num::ParseFloatError| error(e.to_string()))?,
+                    min_score: optional(min_score)
+                        .map(|x| x.parse())
+                        .transpose()
+                        .map_err(|e: std::num::ParseFloatError| error(e.to_string()))?,
+                    occupations: optional(occupations)
+                        .map(|x| x.split(';').map(|x| x.trim().to_string()).collect())
+                        .unwrap_or_default(),
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(Self::sorted(draws))
+    }
+
+    /// reads a `.csv` file, or json otherwise.
+    pub fn read(path: &Path) -> Result<Vec<Self>, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+        match path.extension().and_then(|x| x.to_str()) {
+            Some("csv") => Self::parse_csv(&content),
+            _ => Self::parse_json(&content),
+        }
+        .map_err(|e| format!("failed to parse {}: {}", path.display(), e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Province, ProvincialDraw};
+
+    #[tokio::test]
+    async fn parse() {
+        let csv = "province,date,size,min_score,occupations\n\
+                   Ontario,2024-03-12,1200,,\n\
+                   BC,2024-03-05,150,470,21231; 21232\n";
+        let draws = ProvincialDraw::parse_csv(csv).unwrap();
+        assert_eq!(draws.len(), 2);
+        assert_eq!(draws[0].province, Province::BritishColumbia);
+        assert_eq!(draws[0].min_score, Some(470.0));
+        assert_eq!(draws[0].occupations, vec!["21231", "21232"]);
+        assert_eq!(draws[1].province, Province::Ontario);
+        assert_eq!(draws[1].min_score, None);
+        assert!(draws[1].occupations.is_empty());
+
+        // the json written back reads the same
+        let json = serde_json::to_string(&draws).unwrap();
+        assert!(json.contains(r#""province":"BC""#));
+        assert_eq!(ProvincialDraw::parse_json(&json).unwrap(), draws);
+        let json = r#"[{"province": "SK", "date": "2024-01-09", "size": 50}]"#;
+        assert_eq!(ProvincialDraw::parse_json(json).unwrap()[0].min_score, None);
+
+        let error = ProvincialDraw::parse_csv("province,date,size\nQC,2024-01-09,10").unwrap_err();
+        assert_eq!(error, "line 2: unknown province QC");
+        let csv = "\nprovince,date,size\nON,2024-01-09,10\n\nQC,2024-01-16,10\n";
+        let error = ProvincialDraw::parse_csv(csv).unwrap_err();
+        assert_eq!(error, "line 5: unknown province QC");
+        assert!(ProvincialDraw::parse_csv("province,size\n").is_err());
+    }
+}
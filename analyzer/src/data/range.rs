@@ -0,0 +1,146 @@
+use super::{Invite, Pool};
+use chrono::{Datelike, Days, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Dates restricting the data of a chart, both ends are inclusive and `None` is unbounded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct DateRange {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+impl DateRange {
+    pub const ALL: Self = Self {
+        from: None,
+        to: None,
+    };
+
+    pub fn new(from: Option<NaiveDate>, to: Option<NaiveDate>) -> Self {
+        Self { from, to }
+    }
+
+    /// range of a preset, relative to the latest invitation:
+    /// "all", "1y", "2y" and "5y" (the last years) or "categories" (since the first category draw).
+    pub fn preset(preset: &str, invite_data: &[Invite]) -> Result<Self, String> {
+        let last = invite_data.last().map(|invite| invite.date);
+        let years = |n: u32| last.map(|date| date - Months::new(12 * n) + Days::new(1));
+        match preset {
+            "all" => Ok(Self::ALL),
+            "1y" => Ok(Self::new(years(1), None)),
+            "2y" => Ok(Self::new(years(2), None)),
+            "5y" => Ok(Self::new(years(5), None)),
+            "categories" => Ok(Self::new(
+                invite_data
+                    .iter()
+                    .find(|invite| invite.category.year.is_some())
+                    .map(|invite| invite.date),
+                None,
+            )),
+            _ => Err(format!("invalid date range {}", preset)),
+        }
+    }
+
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.from.is_none_or(|from| from <= date) && self.to.is_none_or(|to| date <= to)
+    }
+
+    /// whether a part of the year is within the range.
+    pub fn contains_year(&self, year: i32) -> bool {
+        self.from.is_none_or(|from| from.year() <= year)
+            && self.to.is_none_or(|to| year <= to.year())
+    }
+
+    // the data are sorted by date
+    pub(super) fn bounds<T>(&self, data: &[T], date: impl Fn(&T) -> NaiveDate) -> (usize, usize) {
+        let start = self
+            .from
+            .map(|from| data.partition_point(|x| date(x) < from))
+            .unwrap_or(0);
+        let end = self
+            .to
+            .map(|to| data.partition_point(|x| date(x) <= to))
+            .unwrap_or(data.len());
+        (start, end.max(start))
+    }
+
+    /// the invitations within the range.
+    pub fn invites<'a>(&self, invite_data: &'a [Invite]) -> &'a [Invite] {
+        let (start, end) = self.bounds(invite_data, |invite| invite.date);
+        &invite_data[start..end]
+    }
+
+    /// the pool distributions within the range.
+    pub fn pools<'a>(&self, pool_data: &'a [Pool]) -> &'a [Pool] {
+        let (start, end) = self.bounds(pool_data, |pool| pool.date);
+        &pool_data[start..end]
+    }
+
+    /// the range starting `days` earlier, for the analyses that need history before their
+    /// first value.
+    pub fn warm_up(self, days: u64) -> Self {
+        Self {
+            from: self.from.map(|from| {
+                from.checked_sub_days(Days::new(days))
+                    .unwrap_or(NaiveDate::MIN)
+            }),
+            to: self.to,
+        }
+    }
+
+    /// the range starting at the pool distribution in effect at its start, so the analyses
+    /// walking the pool see the candidates of the first invitations.
+    pub fn since_prior_pool(self, pool_data: &[Pool]) -> Self {
+        let from = self.from.map(|from| {
+            pool_data
+                .iter()
+                .rev()
+                .map(|pool| pool.date)
+                .find(|date| *date <= from)
+                .unwrap_or(from)
+        });
+        Self { from, to: self.to }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DateRange;
+    use crate::data::test_util::{invites, pools};
+    use chrono::NaiveDate;
+
+    #[tokio::test]
+    async fn range() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+        let invite_data = invites(&[
+            (date(1, 10), "General", 1500.0, 500.0),
+            (date(2, 7), "STEM occupations (2024-1)", 1500.0, 500.0),
+            (date(3, 6), "General", 1500.0, 500.0),
+        ]);
+        let pool_data = pools(&[
+            (date(1, 1), 1000.0),
+            (date(2, 1), 1000.0),
+            (date(3, 1), 1000.0),
+        ]);
+
+        let range = DateRange::new(Some(date(2, 1)), Some(date(3, 1)));
+        assert_eq!(range.invites(&invite_data).len(), 1);
+        assert_eq!(range.pools(&pool_data).len(), 2);
+        assert_eq!(range.warm_up(31).from, Some(date(1, 1)));
+        assert_eq!(range.warm_up(u64::MAX).from, Some(NaiveDate::MIN));
+        assert!(range.contains_year(2024) && !range.contains_year(2023));
+        let range = DateRange::new(Some(date(2, 15)), None).since_prior_pool(&pool_data);
+        assert_eq!(range.from, Some(date(2, 1)));
+
+        assert_eq!(DateRange::preset("all", &invite_data), Ok(DateRange::ALL));
+        assert_eq!(
+            DateRange::preset("1y", &invite_data).unwrap().from,
+            Some(NaiveDate::from_ymd_opt(2023, 3, 7).unwrap())
+        );
+        assert_eq!(
+            DateRange::preset("categories", &invite_data).unwrap().from,
+            Some(date(2, 7))
+        );
+        assert!(DateRange::preset("10y", &invite_data).is_err());
+    }
+}
@@ -1,16 +1,16 @@
 extern crate wasm_bindgen;
 
 use async_once_cell::OnceCell;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct EeRounds123En {
     pub classes: String,
     pub rounds: Vec<RawData>,
 }
 
-#[derive(Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct RawData {
     #[serde(rename = "DrawText1")]
     pub draw_text1: String,
@@ -57,8 +57,11 @@ pub struct RawData {
     pub mitext: String,
 }
 
+pub const IRCC_URL: &str =
+    "https://www.canada.ca/content/dam/ircc/documents/json/ee_rounds_123_en.json";
+
 async fn impl_raw_data() -> EeRounds123En {
-    reqwest::get("https://www.canada.ca/content/dam/ircc/documents/json/ee_rounds_123_en.json")
+    reqwest::get(IRCC_URL)
         .await
         .unwrap_throw()
         .json::<EeRounds123En>()
@@ -0,0 +1,143 @@
+use super::raw::{EeRounds123En, IRCC_URL};
+#[cfg(feature = "sqlite")]
+use super::SqliteStore;
+use super::{all_plan, Invite, Plan, Pool};
+use std::future::Future;
+use std::path::PathBuf;
+
+/// Somewhere the IRCC data can be fetched from, again and again.
+///
+/// Unlike `raw_data`, which fetches once per process and throws on failure, every
+/// call fetches the current data and failures are returned.
+pub trait DrawSource {
+    fn fetch(&self) -> impl Future<Output = Result<EeRounds123En, String>>;
+}
+
+/// The draws, pools and plans the analysis runs on.
+pub type AnalysisData = (Vec<Invite>, Vec<Pool>, Vec<Plan>);
+
+/// Somewhere the `AnalysisData` can be loaded from, again and again.
+///
+/// Every `DrawSource` is one, with the bundled plans.
+pub trait DataSource {
+    fn load(&self) -> impl Future<Output = Result<AnalysisData, String>>;
+}
+
+impl<S: DrawSource> DataSource for S {
+    async fn load(&self) -> Result<AnalysisData, String> {
+        let raw_data = self.fetch().await?;
+        Ok((
+            Invite::parse_all(&raw_data),
+            Pool::parse_all(&raw_data),
+            all_plan().await,
+        ))
+    }
+}
+
+pub struct HttpSource {
+    url: String,
+}
+
+impl HttpSource {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self { url: url.into() }
+    }
+
+    pub fn ircc() -> Self {
+        Self::new(IRCC_URL)
+    }
+}
+
+impl DrawSource for HttpSource {
+    async fn fetch(&self) -> Result<EeRounds123En, String> {
+        reqwest::get(&self.url)
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| format!("failed to fetch {}: {}", self.url, e))?
+            .json::<EeRounds123En>()
+            .await
+            .map_err(|e| format!("failed to parse {}: {}", self.url, e))
+    }
+}
+
+/// A local copy of the IRCC json, e.g. a snapshot of the archive.
+pub struct FileSource {
+    path: PathBuf,
+}
+
+impl FileSource {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl DrawSource for FileSource {
+    async fn fetch(&self) -> Result<EeRounds123En, String> {
+        let content = std::fs::read(&self.path)
+            .map_err(|e| format!("failed to read {}: {}", self.path.display(), e))?;
+        serde_json::from_slice(&content)
+            .map_err(|e| format!("failed to parse {}: {}", self.path.display(), e))
+    }
+}
+
+/// The tables of a database filled by the `sqlite` binary, read again at each load so that
+/// the later imports and the edits of the tables are seen.
+#[cfg(feature = "sqlite")]
+pub struct SqliteSource {
+    path: PathBuf,
+}
+
+#[cfg(feature = "sqlite")]
+impl SqliteSource {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl DataSource for SqliteSource {
+    async fn load(&self) -> Result<AnalysisData, String> {
+        let store = SqliteStore::open(&self.path)
+            .map_err(|e| format!("failed to open {}: {}", self.path.display(), e))?;
+        let (invite_data, pool_data, plan_data) = store
+            .load_invites()
+            .and_then(|invite_data| Ok((invite_data, store.load_pools()?, store.load_plans()?)))
+            .map_err(|e| format!("failed to read {}: {}", self.path.display(), e))?;
+        if invite_data.is_empty() {
+            return Err(format!("no draws imported into {}", self.path.display()));
+        }
+        Ok((invite_data, pool_data, plan_data))
+    }
+}
+
+/// The source given to the binaries: `sqlite:<db>` with the `sqlite` feature, a url, a path,
+/// or the IRCC website if absent.
+pub enum ArgSource {
+    Http(HttpSource),
+    File(FileSource),
+    #[cfg(feature = "sqlite")]
+    Sqlite(SqliteSource),
+}
+
+pub fn source_from_arg(arg: Option<String>) -> ArgSource {
+    match arg {
+        #[cfg(feature = "sqlite")]
+        Some(arg) if arg.starts_with("sqlite:") => {
+            ArgSource::Sqlite(SqliteSource::new(arg.trim_start_matches("sqlite:")))
+        }
+        Some(arg) if !arg.starts_with("http") => ArgSource::File(FileSource::new(arg)),
+        Some(arg) => ArgSource::Http(HttpSource::new(arg)),
+        None => ArgSource::Http(HttpSource::ircc()),
+    }
+}
+
+impl DataSource for ArgSource {
+    async fn load(&self) -> Result<AnalysisData, String> {
+        match self {
+            ArgSource::Http(source) => source.load().await,
+            ArgSource::File(source) => source.load().await,
+            #[cfg(feature = "sqlite")]
+            ArgSource::Sqlite(source) => source.load().await,
+        }
+    }
+}
@@ -0,0 +1,360 @@
+use super::{Category, CategoryCode, Invite, InviteId, Pathway, Plan, Pool};
+use rusqlite::{params, Connection, Result};
+use std::path::Path;
+
+/// Schema changes, applied in order, `PRAGMA user_version` counts the applied ones.
+const MIGRATIONS: &[&str] = &[SCHEMA_V1, SCHEMA_V2];
+
+const SCHEMA_V1: &str = "
+    CREATE TABLE categories (
+        code INTEGER PRIMARY KEY,
+        name TEXT NOT NULL
+    );
+    CREATE TABLE draws (
+        number INTEGER NOT NULL,
+        part INTEGER NOT NULL,
+        date TEXT NOT NULL,
+        category INTEGER NOT NULL REFERENCES categories (code),
+        category_year INTEGER,
+        pnp INTEGER NOT NULL,
+        cec INTEGER NOT NULL,
+        fsw INTEGER NOT NULL,
+        fst INTEGER NOT NULL,
+        size REAL NOT NULL,
+        score REAL NOT NULL,
+        tie_break TEXT,
+        PRIMARY KEY (number, part)
+    );
+    CREATE INDEX draws_date ON draws (date);
+    CREATE TABLE score_ranges (
+        bucket INTEGER PRIMARY KEY,
+        min_score INTEGER NOT NULL,
+        max_score INTEGER NOT NULL,
+        name TEXT NOT NULL
+    );
+    CREATE TABLE pools (
+        date TEXT PRIMARY KEY
+    );
+    CREATE TABLE pool_counts (
+        date TEXT NOT NULL REFERENCES pools (date) ON DELETE CASCADE,
+        bucket INTEGER NOT NULL REFERENCES score_ranges (bucket),
+        count REAL NOT NULL,
+        PRIMARY KEY (date, bucket)
+    );
+    CREATE TABLE plans (
+        year INTEGER PRIMARY KEY,
+        min REAL NOT NULL,
+        max REAL NOT NULL
+    );
+";
+
+const SCHEMA_V2: &str = "
+    CREATE TABLE plan_targets (
+        year INTEGER NOT NULL REFERENCES plans (year) ON DELETE CASCADE,
+        category INTEGER NOT NULL REFERENCES categories (code),
+        target REAL NOT NULL,
+        PRIMARY KEY (year, category)
+    );
+";
+
+/// Local SQLite copy of the parsed draws, pools and plans.
+pub struct SqliteStore {
+    conn: Connection,
+}
+
+impl SqliteStore {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        Self::migrate(Connection::open(path)?)
+    }
+
+    pub fn open_in_memory() -> Result<Self> {
+        Self::migrate(Connection::open_in_memory()?)
+    }
+
+    fn migrate(mut conn: Connection) -> Result<Self> {
+        conn.pragma_update(None, "foreign_keys", true)?;
+        let version: usize = conn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+
+        let tx = conn.transaction()?;
+        for migration in MIGRATIONS.iter().skip(version) {
+            tx.execute_batch(migration)?;
+        }
+        tx.pragma_update(None, "user_version", MIGRATIONS.len())?;
+
+        // lookup tables follow the code
+        for category in CategoryCode::values() {
+            tx.execute(
+                "INSERT INTO categories (code, name) VALUES (?1, ?2)
+                ON CONFLICT (code) DO UPDATE SET name = excluded.name",
+                params![*category as u8, category.as_str()],
+            )?;
+        }
+        for i in 0..Pool::N {
+            tx.execute(
+                "INSERT INTO score_ranges (bucket, min_score, max_score, name) VALUES (?1, ?2, ?3, ?4)
+                ON CONFLICT (bucket) DO UPDATE SET
+                    min_score = excluded.min_score,
+                    max_score = excluded.max_score,
+                    name = excluded.name",
+                params![i, Pool::min_score(i), Pool::max_score(i), Pool::as_str(i)],
+            )?;
+        }
+        tx.commit()?;
+
+        Ok(Self { conn })
+    }
+
+    pub fn save_invites(&mut self, invite_data: &[Invite]) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO draws (number, part, date, category, category_year,
+                    pnp, cec, fsw, fst, size, score, tie_break)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+                ON CONFLICT (number, part) DO UPDATE SET
+                    date = excluded.date,
+                    category = excluded.category,
+                    category_year = excluded.category_year,
+                    pnp = excluded.pnp,
+                    cec = excluded.cec,
+                    fsw = excluded.fsw,
+                    fst = excluded.fst,
+                    size = excluded.size,
+                    score = excluded.score,
+                    tie_break = excluded.tie_break",
+            )?;
+            for invite in invite_data {
+                stmt.execute(params![
+                    i32::from(invite.id),
+                    invite.id.part(),
+                    invite.date,
+                    invite.category.code as u8,
+                    invite.category.year,
+                    invite.pathway.is_pnp(),
+                    invite.pathway.is_cec(),
+                    invite.pathway.is_fsw(),
+                    invite.pathway.is_fst(),
+                    invite.size,
+                    invite.score,
+                    invite.tie_break,
+                ])?;
+            }
+        }
+        tx.commit()
+    }
+
+    pub fn save_pools(&mut self, pool_data: &[Pool]) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        {
+            let mut pool_stmt =
+                tx.prepare("INSERT INTO pools (date) VALUES (?1) ON CONFLICT (date) DO NOTHING")?;
+            let mut count_stmt = tx.prepare(
+                "INSERT INTO pool_counts (date, bucket, count) VALUES (?1, ?2, ?3)
+                ON CONFLICT (date, bucket) DO UPDATE SET count = excluded.count",
+            )?;
+            for pool in pool_data {
+                pool_stmt.execute(params![pool.date])?;
+                for i in 0..Pool::N {
+                    count_stmt.execute(params![pool.date, i, pool.count(i)])?;
+                }
+            }
+        }
+        tx.commit()
+    }
+
+    pub fn save_plans(&mut self, plan_data: &[Plan]) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO plans (year, min, max) VALUES (?1, ?2, ?3)
+                ON CONFLICT (year) DO UPDATE SET min = excluded.min, max = excluded.max",
+            )?;
+            // the targets of a year are replaced as a whole
+            let mut clear_stmt = tx.prepare("DELETE FROM plan_targets WHERE year = ?1")?;
+            let mut target_stmt = tx
+                .prepare("INSERT INTO plan_targets (year, category, target) VALUES (?1, ?2, ?3)")?;
+            for plan in plan_data {
+                stmt.execute(params![plan.year, plan.min, plan.max])?;
+                clear_stmt.execute(params![plan.year])?;
+                for category in CategoryCode::values() {
+                    if let Some(target) = plan.target(*category) {
+                        target_stmt.execute(params![plan.year, *category as u8, target])?;
+                    }
+                }
+            }
+        }
+        tx.commit()
+    }
+
+    // ensure sorted
+    pub fn load_invites(&self) -> Result<Vec<Invite>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT number, part, date, category, category_year,
+                pnp, cec, fsw, fst, size, score, tie_break
+            FROM draws ORDER BY number, part",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            let code: usize = row.get(3)?;
+            Ok(Invite {
+                id: InviteId::new(row.get(0)?, row.get(1)?),
+                date: row.get(2)?,
+                category: Category {
+                    code: CategoryCode::values()
+                        .get(code)
+                        .copied()
+                        .unwrap_or(CategoryCode::Invalid),
+                    year: row.get(4)?,
+                },
+                pathway: Pathway::new(row.get(5)?, row.get(6)?, row.get(7)?, row.get(8)?),
+                size: row.get(9)?,
+                score: row.get(10)?,
+                tie_break: row.get(11)?,
+            })
+        })?;
+        rows.collect()
+    }
+
+    // ensure sorted
+    pub fn load_pools(&self) -> Result<Vec<Pool>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT pools.date, pool_counts.bucket, pool_counts.count
+            FROM pools JOIN pool_counts ON pools.date = pool_counts.date
+            ORDER BY pools.date, pool_counts.bucket",
+        )?;
+        let mut rows = stmt.query([])?;
+
+        let mut pool_data: Vec<Pool> = Vec::new();
+        while let Some(row) = rows.next()? {
+            let date = row.get(0)?;
+            let bucket: usize = row.get(1)?;
+            if pool_data.last().map(|pool| pool.date) != Some(date) {
+                pool_data.push(Pool {
+                    data: [0.0; Pool::N],
+                    date,
+                });
+            }
+            if bucket < Pool::N {
+                pool_data.last_mut().unwrap().data[bucket] = row.get(2)?;
+            }
+        }
+        Ok(pool_data)
+    }
+
+    // ensure sorted
+    pub fn load_plans(&self) -> Result<Vec<Plan>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT year, min, max FROM plans ORDER BY year")?;
+        let rows = stmt.query_map([], |row| {
+            Ok(Plan::new(row.get(0)?, row.get(1)?, row.get(2)?))
+        })?;
+        let mut plan_data = rows.collect::<Result<Vec<_>>>()?;
+
+        let mut stmt = self
+            .conn
+            .prepare("SELECT year, category, target FROM plan_targets")?;
+        let mut rows = stmt.query([])?;
+        while let Some(row) = rows.next()? {
+            let year: i32 = row.get(0)?;
+            let code: usize = row.get(1)?;
+            let plan = plan_data.iter_mut().find(|plan| plan.year == year);
+            if let (Some(plan), Some(category)) = (plan, CategoryCode::values().get(code)) {
+                *plan = plan.with_target(*category, row.get(2)?);
+            }
+        }
+        Ok(plan_data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SqliteStore;
+    use crate::data::test_util::{invites, pools};
+    use crate::data::{
+        Category, CategoryCode, DataSource, Invite, InviteId, Pathway, Plan, Pool, SqliteSource,
+    };
+    use chrono::NaiveDate;
+
+    #[tokio::test]
+    async fn upsert() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+        let invite = |id, size| Invite {
+            id,
+            date: date(1, 10),
+            category: Category::parse("French language proficiency (2023-1)"),
+            pathway: Pathway::parse("Canadian Experience Class, Federal Skilled Worker"),
+            size,
+            score: 365.0,
+            tie_break: date(1, 2).and_hms_opt(9, 30, 0),
+        };
+        let mut pool = Pool {
+            data: [0.0; Pool::N],
+            date: date(1, 8),
+        };
+        pool.data[14] = 1200.0;
+
+        let mut store = SqliteStore::open_in_memory().unwrap();
+        store
+            .save_invites(&[invite(InviteId::parse("91b"), 1000.0)])
+            .unwrap();
+        store
+            .save_invites(&[
+                invite(InviteId::parse("91b"), 1500.0),
+                invite(InviteId::parse("91a"), 500.0),
+            ])
+            .unwrap();
+        store.save_pools(&[pool, pool]).unwrap();
+        let plan = Plan::new(2024, 1.0, 2.0).with_target(CategoryCode::French, 0.5);
+        store
+            .save_plans(&[plan.with_target(CategoryCode::Health, 0.2)])
+            .unwrap();
+        store.save_plans(&[plan]).unwrap();
+
+        let invite_data = store.load_invites().unwrap();
+        assert_eq!(invite_data.len(), 2);
+        assert_eq!(invite_data[0].id, InviteId::parse("91a"));
+        assert_eq!(invite_data[1].size, 1500.0);
+        assert_eq!(
+            invite_data[1].category,
+            invite(InviteId::parse("91b"), 0.0).category
+        );
+        assert_eq!(
+            invite_data[1].pathway,
+            invite(InviteId::parse("91b"), 0.0).pathway
+        );
+        assert_eq!(invite_data[1].tie_break, date(1, 2).and_hms_opt(9, 30, 0));
+
+        let pool_data = store.load_pools().unwrap();
+        assert_eq!(pool_data.len(), 1);
+        assert_eq!(pool_data[0].data, pool.data);
+        assert_eq!(store.load_plans().unwrap(), vec![plan]);
+    }
+
+    #[tokio::test]
+    async fn source() {
+        let path = std::env::temp_dir().join(format!("analyzer-store-{}.db", std::process::id()));
+        let source = SqliteSource::new(&path);
+        assert!(source.load().await.is_err());
+
+        let date = NaiveDate::from_ymd_opt(2024, 1, 10).unwrap();
+        let plan = Plan::new(2024, 1.0, 2.0).with_target(CategoryCode::French, 0.5);
+        let mut store = SqliteStore::open(&path).unwrap();
+        store
+            .save_invites(&invites(&[(date, "General", 1500.0, 500.0)]))
+            .unwrap();
+        store.save_pools(&pools(&[(date, 1000.0)])).unwrap();
+        store.save_plans(&[plan]).unwrap();
+        // the edits of the tables are analyzed
+        store
+            .conn
+            .execute("UPDATE draws SET score = 490.0", [])
+            .unwrap();
+
+        let (invite_data, pool_data, plan_data) = source.load().await.unwrap();
+        assert_eq!(invite_data.len(), 1);
+        assert_eq!(invite_data[0].score, 490.0);
+        assert_eq!(pool_data.len(), 1);
+        assert_eq!(plan_data, vec![plan]);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
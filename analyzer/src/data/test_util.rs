@@ -0,0 +1,28 @@
+use super::{Category, Invite, InviteId, Pathway, Pool};
+use chrono::NaiveDate;
+
+/// the draws of `(date, category, size, score)`, numbered from 300.
+pub fn invites(data: &[(NaiveDate, &str, f64, f64)]) -> Vec<Invite> {
+    data.iter()
+        .enumerate()
+        .map(|(i, (date, name, size, score))| Invite {
+            id: InviteId::new(300 + i as i32, 0),
+            date: *date,
+            category: Category::parse(name),
+            pathway: Pathway::parse("Canadian Experience Class, Federal Skilled Worker"),
+            size: *size,
+            score: *score,
+            tie_break: None,
+        })
+        .collect()
+}
+
+/// the pool distributions of `(date, count)`, with the same count in every score range.
+pub fn pools(data: &[(NaiveDate, f64)]) -> Vec<Pool> {
+    data.iter()
+        .map(|(date, count)| Pool {
+            data: [*count; Pool::N],
+            date: *date,
+        })
+        .collect()
+}
@@ -0,0 +1,307 @@
+use crate::analyze::category::CategoryAnalyzer;
+use crate::analyze::config::AnalysisConfig;
+use crate::analyze::predict::Predictor;
+use crate::analyze::rate::RateAnalyzer;
+use crate::data::{CategoryCode, Invite, Plan, Pool};
+use arrow::array::{
+    ArrayRef, BooleanArray, Date32Array, Float64Array, Int32Array, Int64Array, StringArray,
+    TimestampMillisecondArray,
+};
+use arrow::datatypes::Date32Type;
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+use chrono::NaiveDate;
+use itertools::Itertools;
+use parquet::arrow::ArrowWriter;
+use parquet::errors::ParquetError;
+use std::collections::HashSet;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+fn dates(dates: impl IntoIterator<Item = NaiveDate>) -> ArrayRef {
+    Arc::new(Date32Array::from_iter_values(
+        dates.into_iter().map(Date32Type::from_naive_date),
+    ))
+}
+
+fn floats(values: impl IntoIterator<Item = f64>) -> ArrayRef {
+    Arc::new(Float64Array::from_iter_values(values))
+}
+
+fn strings(values: impl IntoIterator<Item = String>) -> ArrayRef {
+    Arc::new(StringArray::from_iter_values(values))
+}
+
+fn bools(values: impl IntoIterator<Item = bool>) -> ArrayRef {
+    Arc::new(BooleanArray::from_iter(values.into_iter().map(Some)))
+}
+
+pub fn invites(invite_data: &[Invite]) -> Result<RecordBatch, ArrowError> {
+    RecordBatch::try_from_iter_with_nullable([
+        (
+            "id",
+            strings(invite_data.iter().map(|x| x.id.to_string())),
+            false,
+        ),
+        ("date", dates(invite_data.iter().map(|x| x.date)), false),
+        (
+            "category",
+            strings(invite_data.iter().map(|x| x.category.code.as_str())),
+            false,
+        ),
+        (
+            "category_year",
+            Arc::new(Int32Array::from_iter(
+                invite_data.iter().map(|x| x.category.year),
+            )) as ArrayRef,
+            true,
+        ),
+        (
+            "pnp",
+            bools(invite_data.iter().map(|x| x.pathway.is_pnp())),
+            false,
+        ),
+        (
+            "cec",
+            bools(invite_data.iter().map(|x| x.pathway.is_cec())),
+            false,
+        ),
+        (
+            "fsw",
+            bools(invite_data.iter().map(|x| x.pathway.is_fsw())),
+            false,
+        ),
+        (
+            "fst",
+            bools(invite_data.iter().map(|x| x.pathway.is_fst())),
+            false,
+        ),
+        ("size", floats(invite_data.iter().map(|x| x.size)), false),
+        ("score", floats(invite_data.iter().map(|x| x.score)), false),
+        (
+            "tie_break",
+            Arc::new(TimestampMillisecondArray::from_iter(
+                invite_data
+                    .iter()
+                    .map(|x| x.tie_break.map(|time| time.and_utc().timestamp_millis())),
+            )) as ArrayRef,
+            true,
+        ),
+    ])
+}
+
+// one row per score range, the same layout is used by the pools and the rates
+fn score_ranges<T: Copy>(
+    labels: &[NaiveDate],
+    values: &[T],
+    count: impl Fn(T, usize) -> f64,
+    column: &str,
+) -> Result<RecordBatch, ArrowError> {
+    let capacity = labels.len() * Pool::N;
+    let mut dates_ = Vec::with_capacity(capacity);
+    let mut min_scores = Vec::with_capacity(capacity);
+    let mut max_scores = Vec::with_capacity(capacity);
+    let mut counts = Vec::with_capacity(capacity);
+    for (date, value) in labels.iter().zip(values) {
+        for i in 0..Pool::N {
+            dates_.push(*date);
+            min_scores.push(Pool::min_score(i));
+            max_scores.push(Pool::max_score(i));
+            counts.push(count(*value, i));
+        }
+    }
+
+    RecordBatch::try_from_iter_with_nullable([
+        ("date", dates(dates_), false),
+        (
+            "min_score",
+            Arc::new(Int64Array::from(min_scores)) as ArrayRef,
+            false,
+        ),
+        (
+            "max_score",
+            Arc::new(Int64Array::from(max_scores)) as ArrayRef,
+            false,
+        ),
+        (column, floats(counts), false),
+    ])
+}
+
+pub fn pools(pool_data: &[Pool]) -> Result<RecordBatch, ArrowError> {
+    let labels: Vec<_> = pool_data.iter().map(|pool| pool.date).collect();
+    score_ranges(&labels, pool_data, |pool, i| pool.count(i), "count")
+}
+
+/// daily increase of the pool, see `RateAnalyzer::pool_increase_rate`.
+fn rates(
+    config: &AnalysisConfig,
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+) -> Result<RecordBatch, ArrowError> {
+    let (labels, values) = RateAnalyzer::pool_increase_rate(config, pool_data, invite_data);
+    score_ranges(&labels, &values, |rate, i| rate[i], "rate")
+}
+
+// one row per category
+fn categories<T: Copy>(
+    labels: &[NaiveDate],
+    values: &[T],
+    categories: &HashSet<CategoryCode>,
+    value: impl Fn(T, CategoryCode) -> f64,
+    column: &str,
+) -> Result<RecordBatch, ArrowError> {
+    let categories: Vec<_> = categories
+        .iter()
+        .copied()
+        .sorted_by_key(|category| *category as usize)
+        .collect();
+    let (dates_, (names, values)): (Vec<_>, (Vec<_>, Vec<_>)) = labels
+        .iter()
+        .zip(values)
+        .flat_map(|(date, pool)| {
+            categories
+                .iter()
+                .map(|category| (*date, (category.as_str(), value(*pool, *category))))
+                .collect::<Vec<_>>()
+        })
+        .unzip();
+
+    RecordBatch::try_from_iter_with_nullable([
+        ("date", dates(dates_), false),
+        ("category", strings(names), false),
+        (column, floats(values), false),
+    ])
+}
+
+/// cumulative ITAs per category, see `CategoryAnalyzer::invite_per_category`.
+fn category_invites(
+    config: &AnalysisConfig,
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+) -> Result<RecordBatch, ArrowError> {
+    let (labels, values, codes) =
+        CategoryAnalyzer::invite_per_category(config, pool_data, invite_data);
+    categories(&labels, &values, &codes, |pool, i| pool[i], "invited")
+}
+
+/// share of the pool above the cutoff invited by each category, see
+/// `CategoryAnalyzer::percent_per_category`.
+fn category_percents(
+    config: &AnalysisConfig,
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+) -> Result<RecordBatch, ArrowError> {
+    let (labels, values, codes) =
+        CategoryAnalyzer::percent_per_category(config, pool_data, invite_data);
+    categories(&labels, &values, &codes, |pool, i| pool[i], "percent")
+}
+
+fn predictions(
+    config: &AnalysisConfig,
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+    plan_data: &[Plan],
+) -> Result<RecordBatch, ArrowError> {
+    if pool_data.is_empty() || invite_data.is_empty() {
+        return categories::<f64>(&[], &[], &HashSet::new(), |x, _| x, "score");
+    }
+    let (labels, values, codes) = Predictor::predict(config, pool_data, invite_data, plan_data);
+    categories(&labels, &values, &codes, |pool, i| pool[i], "score")
+}
+
+/// every table of the analysis, by name.
+pub fn record_batches(
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+    plan_data: &[Plan],
+) -> Result<Vec<(&'static str, RecordBatch)>, ArrowError> {
+    let config = AnalysisConfig::current();
+    Ok(vec![
+        ("invites", invites(invite_data)?),
+        ("pools", pools(pool_data)?),
+        ("rates", rates(&config, pool_data, invite_data)?),
+        (
+            "category_invites",
+            category_invites(&config, pool_data, invite_data)?,
+        ),
+        (
+            "category_percents",
+            category_percents(&config, pool_data, invite_data)?,
+        ),
+        (
+            "predictions",
+            predictions(&config, pool_data, invite_data, plan_data)?,
+        ),
+    ])
+}
+
+/// writes each table into `<dir>/<name>.parquet`.
+pub fn write_parquet(dir: &Path, batches: &[(&str, RecordBatch)]) -> Result<(), ParquetError> {
+    std::fs::create_dir_all(dir)?;
+    for (name, batch) in batches {
+        let file = File::create(dir.join(format!("{}.parquet", name)))?;
+        let mut writer = ArrowWriter::try_new(file, batch.schema(), None)?;
+        writer.write(batch)?;
+        writer.close()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{record_batches, write_parquet};
+    use crate::data::test_util::{invites, pools};
+    use crate::data::{Plan, Pool};
+    use arrow::datatypes::DataType;
+    use chrono::NaiveDate;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    #[tokio::test]
+    async fn export() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+        let invite_data = invites(&[
+            (date(1, 10), "General", 1500.0, 520.0),
+            (date(1, 24), "General", 1500.0, 510.0),
+            (date(2, 7), "General", 1500.0, 500.0),
+        ]);
+        let pool_data = pools(&[
+            (date(1, 10), 1000.0),
+            (date(1, 24), 1100.0),
+            (date(2, 7), 1200.0),
+        ]);
+        let plan_data = vec![Plan::new(2024, 90000.0, 116000.0)];
+
+        let batches = record_batches(&pool_data, &invite_data, &plan_data).unwrap();
+        let (_, invites) = &batches[0];
+        assert_eq!(invites.num_rows(), 3);
+        assert_eq!(
+            invites
+                .schema()
+                .field_with_name("date")
+                .unwrap()
+                .data_type(),
+            &DataType::Date32
+        );
+        assert!(invites
+            .schema()
+            .field_with_name("tie_break")
+            .unwrap()
+            .is_nullable());
+        let (_, pools) = &batches[1];
+        assert_eq!(pools.num_rows(), 3 * Pool::N);
+
+        let dir = std::env::temp_dir().join(format!("analyzer-export-{}", std::process::id()));
+        write_parquet(&dir, &batches).unwrap();
+        let file = std::fs::File::open(dir.join("pools.parquet")).unwrap();
+        let rows: usize = ParquetRecordBatchReaderBuilder::try_new(file)
+            .unwrap()
+            .build()
+            .unwrap()
+            .map(|batch| batch.unwrap().num_rows())
+            .sum();
+        assert_eq!(rows, 3 * Pool::N);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
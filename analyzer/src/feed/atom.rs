@@ -0,0 +1,83 @@
+use super::{draw_description, draw_title};
+use crate::data::Invite;
+
+fn escape(x: &str) -> String {
+    x.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Atom feed of the draws, latest first, `site` is the url of the dashboard.
+pub fn atom_feed(invite_data: &[Invite], site: &str) -> String {
+    let site = site.trim_end_matches('/');
+    // the feed only changes with the draws, keep it reproducible
+    let updated = invite_data
+        .last()
+        .map(|invite| format!("{}T00:00:00Z", invite.date.format("%Y-%m-%d")))
+        .unwrap_or_else(|| "1970-01-01T00:00:00Z".into());
+
+    let mut feed = String::new();
+    feed.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    feed.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    feed.push_str("  <title>Express Entry Draws</title>\n");
+    feed.push_str(&format!("  <id>{}/</id>\n", escape(site)));
+    feed.push_str(&format!("  <link href=\"{}/\"/>\n", escape(site)));
+    feed.push_str(&format!(
+        "  <link rel=\"self\" href=\"{}/draws.atom\"/>\n",
+        escape(site)
+    ));
+    feed.push_str(&format!("  <updated>{}</updated>\n", updated));
+    feed.push_str("  <author><name>Express Entry Dashboard</name></author>\n");
+
+    for invite in invite_data.iter().rev() {
+        let date = invite.date.format("%Y-%m-%d");
+        feed.push_str("  <entry>\n");
+        feed.push_str(&format!(
+            "    <title>{}</title>\n",
+            escape(&draw_title(invite))
+        ));
+        feed.push_str(&format!(
+            "    <id>{}/#draw-{}</id>\n",
+            escape(site),
+            invite.id
+        ));
+        feed.push_str(&format!("    <updated>{}T00:00:00Z</updated>\n", date));
+        feed.push_str(&format!(
+            "    <content type=\"text\">{}</content>\n",
+            escape(&draw_description(invite))
+        ));
+        feed.push_str("  </entry>\n");
+    }
+
+    feed.push_str("</feed>\n");
+    feed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::atom_feed;
+    use crate::data::{Category, Invite, InviteId, Pathway};
+    use chrono::NaiveDate;
+
+    #[tokio::test]
+    async fn atom() {
+        let invite_data = vec![Invite {
+            id: InviteId::parse("91a"),
+            date: NaiveDate::from_ymd_opt(2024, 1, 10).unwrap(),
+            category: Category::parse("Trade occupations (2023-1)"),
+            pathway: Pathway::parse("Federal Skilled Trades"),
+            size: 1500.0,
+            score: 388.0,
+            tie_break: None,
+        }];
+
+        let feed = atom_feed(&invite_data, "https://example.com/");
+        assert!(feed.contains("<updated>2024-01-10T00:00:00Z</updated>"));
+        assert!(feed.contains("<id>https://example.com/#draw-91a</id>"));
+        assert!(feed.contains("<title>Draw 91a: 1500 Trade ITAs, CRS 388</title>"));
+        assert!(feed.contains("Tie-break: not published"));
+        assert_eq!(feed.matches("<entry>").count(), 1);
+    }
+}
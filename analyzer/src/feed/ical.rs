@@ -0,0 +1,169 @@
+use super::{draw_description, draw_title};
+use crate::analyze::config::AnalysisConfig;
+use crate::analyze::predict::Predictor;
+use crate::data::{Invite, Plan, Pool};
+
+/// number of predicted draws added as tentative events.
+const FORECAST_STEPS: usize = 3;
+
+fn escape(x: &str) -> String {
+    x.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+// content lines are limited to 75 octets, longer ones continue after a CRLF and a space
+fn push_line(calendar: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            calendar.push_str("\r\n ");
+            width = 1;
+        }
+        calendar.push(c);
+        width += c.len_utf8();
+    }
+    calendar.push_str("\r\n");
+}
+
+/// iCalendar of the draws, followed by the upcoming predicted draws as tentative events.
+pub fn icalendar(pool_data: &[Pool], invite_data: &[Invite], plan_data: &[Plan]) -> String {
+    // the calendar only changes with the draws, keep it reproducible
+    let stamp = invite_data
+        .last()
+        .map(|invite| format!("{}T000000Z", invite.date.format("%Y%m%d")))
+        .unwrap_or_else(|| "19700101T000000Z".into());
+
+    let mut calendar = String::new();
+    push_line(&mut calendar, "BEGIN:VCALENDAR");
+    push_line(&mut calendar, "VERSION:2.0");
+    push_line(
+        &mut calendar,
+        "PRODID:-//Express Entry Dashboard//Draws//EN",
+    );
+    push_line(&mut calendar, "X-WR-CALNAME:Express Entry Draws");
+
+    for invite in invite_data {
+        let date = invite.date.format("%Y%m%d");
+        push_line(&mut calendar, "BEGIN:VEVENT");
+        push_line(
+            &mut calendar,
+            &format!("UID:draw-{}@express-entry", invite.id),
+        );
+        push_line(&mut calendar, &format!("DTSTAMP:{}", stamp));
+        push_line(&mut calendar, &format!("DTSTART;VALUE=DATE:{}", date));
+        push_line(&mut calendar, "STATUS:CONFIRMED");
+        push_line(
+            &mut calendar,
+            &format!("SUMMARY:{}", escape(&draw_title(invite))),
+        );
+        push_line(
+            &mut calendar,
+            &format!("DESCRIPTION:{}", escape(&draw_description(invite))),
+        );
+        push_line(&mut calendar, "END:VEVENT");
+    }
+
+    let forecast = Predictor::upcoming(
+        &AnalysisConfig::current(),
+        pool_data,
+        invite_data,
+        plan_data,
+        FORECAST_STEPS,
+    );
+    for (date, category, score) in forecast {
+        push_line(&mut calendar, "BEGIN:VEVENT");
+        push_line(
+            &mut calendar,
+            &format!(
+                "UID:forecast-{}-{}@express-entry",
+                date.format("%Y%m%d"),
+                category.as_str()
+            ),
+        );
+        push_line(&mut calendar, &format!("DTSTAMP:{}", stamp));
+        push_line(
+            &mut calendar,
+            &format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")),
+        );
+        push_line(&mut calendar, "STATUS:TENTATIVE");
+        push_line(
+            &mut calendar,
+            &format!(
+                "SUMMARY:{}",
+                escape(&format!(
+                    "Forecast {} draw, CRS about {}",
+                    category.as_str(),
+                    score
+                ))
+            ),
+        );
+        push_line(&mut calendar, "END:VEVENT");
+    }
+
+    push_line(&mut calendar, "END:VCALENDAR");
+    calendar
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{icalendar, push_line};
+    use crate::data::test_util::{invites, pools};
+    use crate::data::{Category, Invite, InviteId, Pathway, Plan};
+    use chrono::NaiveDate;
+
+    #[tokio::test]
+    async fn ical() {
+        let invite_data = vec![Invite {
+            id: InviteId::parse("300"),
+            date: NaiveDate::from_ymd_opt(2024, 6, 19).unwrap(),
+            category: Category::parse("Canadian Experience Class"),
+            pathway: Pathway::parse("Canadian Experience Class"),
+            size: 1499.0,
+            score: 522.0,
+            tie_break: NaiveDate::from_ymd_opt(2024, 6, 1)
+                .unwrap()
+                .and_hms_opt(12, 30, 0),
+        }];
+
+        let calendar = icalendar(&[], &invite_data, &[]);
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+        assert!(calendar.contains("UID:draw-300@express-entry\r\n"));
+        assert!(calendar.contains("DTSTART;VALUE=DATE:20240619\r\n"));
+        assert!(calendar.contains("SUMMARY:Draw 300: 1499 CEC ITAs\\, CRS 522\r\n"));
+        assert!(!calendar.contains("TENTATIVE"));
+
+        let mut folded = String::new();
+        push_line(&mut folded, &"x".repeat(100));
+        assert_eq!(
+            folded,
+            format!("{}\r\n {}\r\n", "x".repeat(75), "x".repeat(25))
+        );
+    }
+
+    #[tokio::test]
+    async fn forecast() {
+        let date = |month, day| NaiveDate::from_ymd_opt(2024, month, day).unwrap();
+        let invite_data = invites(&[
+            (date(1, 10), "General", 1500.0, 520.0),
+            (date(1, 24), "General", 1500.0, 510.0),
+            (date(2, 7), "General", 1500.0, 500.0),
+        ]);
+        let pool_data = pools(&[
+            (date(1, 10), 1000.0),
+            (date(1, 24), 1100.0),
+            (date(2, 7), 1200.0),
+        ]);
+        let plan_data = vec![Plan::new(2024, 90000.0, 116000.0)];
+
+        // the next draws two weeks apart, after the confirmed ones
+        let calendar = icalendar(&pool_data, &invite_data, &plan_data);
+        assert_eq!(calendar.matches("STATUS:TENTATIVE\r\n").count(), 3);
+        let event = calendar.split("BEGIN:VEVENT\r\n").nth(4).unwrap();
+        assert!(event.starts_with("UID:forecast-20240221-General@express-entry\r\n"));
+        assert!(event.contains("DTSTART;VALUE=DATE:20240221\r\nSTATUS:TENTATIVE\r\n"));
+        assert!(event.contains("SUMMARY:Forecast General draw\\, CRS about 451\r\n"));
+    }
+}
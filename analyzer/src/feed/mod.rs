@@ -0,0 +1,31 @@
+mod atom;
+mod ical;
+
+pub use atom::atom_feed;
+pub use ical::icalendar;
+
+use crate::data::Invite;
+
+fn draw_title(invite: &Invite) -> String {
+    format!(
+        "Draw {}: {} {} ITAs, CRS {}",
+        invite.id,
+        invite.size,
+        invite.category.code.as_str(),
+        invite.score
+    )
+}
+
+fn draw_description(invite: &Invite) -> String {
+    let tie_break = invite
+        .tie_break
+        .map(|time| format!("{} UTC", time.format("%Y-%m-%d %H:%M:%S")))
+        .unwrap_or_else(|| "not published".into());
+    format!(
+        "Category: {}\nInvitations: {}\nCutoff score: {}\nTie-break: {}",
+        invite.category.code.as_str(),
+        invite.size,
+        invite.score,
+        tie_break
+    )
+}
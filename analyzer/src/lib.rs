@@ -1,6 +1,15 @@
 mod analyze;
 pub mod chart;
 pub mod data;
+#[cfg(feature = "export")]
+pub mod export;
+pub mod feed;
+#[cfg(feature = "render")]
+pub mod render;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "watch")]
+pub mod watch;
 
 #[allow(unused_imports, unused_macros)]
 pub(crate) mod utils {
@@ -0,0 +1,633 @@
+use crate::chart;
+use crate::chart::dataset::Label;
+use crate::chart::figure::{Figure, Mark, Series};
+use crate::data::{DateRange, Invite, Plan, Pool, ProvincialDraw};
+use chrono::{DateTime, Datelike};
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use plotters::style::register_font;
+use std::path::Path;
+use std::sync::Once;
+
+static FONT: &[u8] = include_bytes!("../../fonts/DejaVuSans.ttf");
+const LEGEND_WIDTH: u32 = 200;
+
+/// Size and colors of the rendered charts.
+#[derive(Debug, Clone, Copy)]
+pub struct Style {
+    pub width: u32,
+    pub height: u32,
+    pub background: RGBColor,
+    pub foreground: RGBColor,
+}
+
+impl Style {
+    /// the colors of the dashboard.
+    pub const DARK: Self = Self {
+        width: 1200,
+        height: 600,
+        background: RGBColor(24, 24, 28),
+        foreground: RGBColor(224, 224, 224),
+    };
+
+    pub const LIGHT: Self = Self {
+        width: 1200,
+        height: 600,
+        background: RGBColor(255, 255, 255),
+        foreground: RGBColor(32, 32, 32),
+    };
+}
+
+// the text is laid out with the bundled font, the output does not depend on the system fonts
+fn register() {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(|| {
+        if register_font("sans-serif", FontStyle::Normal, FONT).is_err() {
+            panic!("invalid bundled font");
+        }
+    });
+}
+
+fn color(hex: &str) -> RGBColor {
+    let value = u32::from_str_radix(hex.trim_start_matches('#'), 16).unwrap_or(0);
+    RGBColor((value >> 16) as u8, (value >> 8) as u8, value as u8)
+}
+
+fn error(e: impl std::fmt::Display) -> String {
+    e.to_string()
+}
+
+// series with a value to show, in the legend
+fn is_shown(series: &Series) -> bool {
+    series.legend && series.values.iter().flatten().any(|value| *value != 0.0)
+}
+
+fn draw_legend<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    entries: &[(String, RGBColor)],
+    style: &Style,
+) -> Result<(), String> {
+    let font = ("sans-serif", 12).into_font().color(&style.foreground);
+    for (i, (name, color)) in entries.iter().enumerate() {
+        let y = 10 + 18 * i as i32;
+        area.draw(&Rectangle::new([(0, y), (12, y + 12)], color.filled()))
+            .map_err(error)?;
+        area.draw(&Text::new(name.as_str(), (18, y), font.clone()))
+            .map_err(error)?;
+    }
+    Ok(())
+}
+
+fn draw_pie<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    figure: &Figure,
+    series: &Series,
+    style: &Style,
+) -> Result<(), String> {
+    let (width, height) = area.dim_in_pixel();
+    let center = (width as i32 / 2, height as i32 / 2);
+    let radius = f64::from(width.min(height)) * 0.4;
+    let sizes: Vec<_> = series.values.iter().map(|x| x.unwrap_or(0.0)).collect();
+    let colors: Vec<_> = series.colors.iter().map(|x| color(x)).collect();
+    let labels: Vec<_> = figure.labels.iter().map(label_text).collect();
+
+    let mut pie = Pie::new(&center, &radius, &sizes, &colors, &labels);
+    pie.start_angle(-90.0);
+    pie.donut_hole(radius * 0.5);
+    pie.label_style(("sans-serif", 14).into_font().color(&style.foreground));
+    pie.percentages(("sans-serif", 14).into_font().color(&style.background));
+    area.draw(&pie).map_err(error)
+}
+
+fn label_text(label: &Label) -> String {
+    match label {
+        Label::String(value) => value.clone(),
+        Label::Number(value) => value.to_string(),
+    }
+}
+
+// the range of the y axis, from zero if the values have a base
+fn y_range(values: impl Iterator<Item = f64>, has_base: bool) -> std::ops::Range<f64> {
+    let (y_min, y_max) = values.fold(
+        if has_base {
+            (0.0, 0.0)
+        } else {
+            (f64::MAX, f64::MIN)
+        },
+        |(min, max), value| (min.min(value), max.max(value)),
+    );
+    let padding = ((y_max - y_min) * 0.05).max(1.0);
+    if has_base && y_min >= 0.0 {
+        0.0..y_max + padding
+    } else {
+        y_min - padding..y_max + padding
+    }
+}
+
+fn draw_cartesian<DB: DrawingBackend>(
+    area: &DrawingArea<DB, Shift>,
+    figure: &Figure,
+    style: &Style,
+) -> Result<(), String> {
+    // timestamps, or the index of the label
+    let temporal = figure.temporal;
+    let xs: Vec<f64> = figure
+        .labels
+        .iter()
+        .enumerate()
+        .map(|(i, label)| match label {
+            Label::Number(value) if temporal => *value,
+            _ => i as f64,
+        })
+        .collect();
+
+    let bars: Vec<_> = figure
+        .series
+        .iter()
+        .filter(|series| series.mark == Mark::Bar)
+        .collect();
+    let mut stacks: Vec<_> = bars.iter().map(|series| series.stack.clone()).collect();
+    stacks.dedup();
+    let bar_width = xs
+        .windows(2)
+        .map(|x| x[1] - x[0])
+        .filter(|x| *x > 0.0)
+        .min_by(|a, b| a.total_cmp(b))
+        .unwrap_or(1.0)
+        * 0.8;
+
+    // the bars of each stack are drawn on top of each other
+    let mut tops = vec![vec![0.0; xs.len()]; stacks.len()];
+    let mut bar_rects = Vec::new();
+    for series in &bars {
+        let group = stacks.iter().position(|x| *x == series.stack).unwrap_or(0);
+        let width = bar_width / stacks.len() as f64;
+        for (i, value) in series.values.iter().enumerate() {
+            let value = value.unwrap_or(0.0);
+            let left = xs[i] - bar_width / 2.0 + width * group as f64;
+            let base = tops[group][i];
+            tops[group][i] += value;
+            bar_rects.push((
+                [(left, base), (left + width, base + value)],
+                color(&series.colors[0]),
+            ));
+        }
+    }
+    let values = figure
+        .series
+        .iter()
+        .filter(|series| series.mark != Mark::Bar && !series.secondary)
+        .flat_map(|series| series.values.iter().flatten().copied())
+        .chain(tops.iter().flatten().copied());
+    let has_base = !bars.is_empty() || figure.series.iter().any(|x| x.mark == Mark::Area);
+    let primary = y_range(values, has_base);
+    let secondary: Vec<_> = figure
+        .series
+        .iter()
+        .filter(|series| series.secondary)
+        .flat_map(|series| series.values.iter().flatten().copied())
+        .collect();
+    // the series with their own x, e.g. the points, span them
+    let own_xs = figure
+        .series
+        .iter()
+        .flat_map(|series| series.xs.iter().copied());
+    let (x_min, x_max) = own_xs.fold(
+        (
+            xs.first().copied().unwrap_or(f64::MAX),
+            xs.last().copied().unwrap_or(f64::MIN),
+        ),
+        |(min, max), x| (min.min(x), max.max(x)),
+    );
+    let (x_min, x_max) = if x_min > x_max {
+        (0.0, 1.0)
+    } else {
+        (x_min, x_max)
+    };
+    // the points keep clear of the axes as the values do
+    let padding = if !bars.is_empty() {
+        bar_width
+    } else if figure.labels.is_empty() {
+        (x_max - x_min) * 0.05
+    } else {
+        0.0
+    };
+    let (x_min, x_max) = (x_min - padding, x_max + padding);
+    let x_range = if x_min < x_max {
+        x_min..x_max
+    } else {
+        x_min - 1.0..x_max + 1.0
+    };
+
+    let mut chart = ChartBuilder::on(area)
+        .margin(10)
+        .x_label_area_size(30)
+        .y_label_area_size(60)
+        .right_y_label_area_size(if secondary.is_empty() { 0 } else { 60 })
+        .build_cartesian_2d(x_range.clone(), primary)
+        .map_err(error)?
+        .set_secondary_coord(x_range, y_range(secondary.iter().copied(), true));
+
+    let x_formatter = |x: &f64| {
+        if temporal {
+            DateTime::from_timestamp_millis(*x as i64)
+                .map(|time| time.format("%Y-%m").to_string())
+                .unwrap_or_default()
+        } else if figure.labels.is_empty() {
+            format!("{}", x)
+        } else {
+            figure
+                .labels
+                .get(x.round() as usize)
+                .filter(|_| (x - x.round()).abs() < 1e-6)
+                .map(label_text)
+                .unwrap_or_default()
+        }
+    };
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .bold_line_style(style.foreground.mix(0.15))
+        .light_line_style(TRANSPARENT)
+        .axis_style(style.foreground)
+        .label_style(("sans-serif", 12).into_font().color(&style.foreground))
+        .x_labels(if temporal || xs.is_empty() {
+            10
+        } else {
+            xs.len().min(20)
+        })
+        .x_label_formatter(&x_formatter)
+        .draw()
+        .map_err(error)?;
+    if !secondary.is_empty() {
+        chart
+            .configure_secondary_axes()
+            .axis_style(style.foreground)
+            .label_style(("sans-serif", 12).into_font().color(&style.foreground))
+            .draw()
+            .map_err(error)?;
+    }
+
+    chart
+        .draw_series(
+            bar_rects
+                .into_iter()
+                .map(|(rect, color)| Rectangle::new(rect, color.filled())),
+        )
+        .map_err(error)?;
+
+    // the first series are drawn on top, as in chart.js
+    for series in figure.series.iter().rev() {
+        macro_rules! draw_series {
+            ($elements:expr) => {
+                if series.secondary {
+                    chart.draw_secondary_series($elements)
+                } else {
+                    chart.draw_series($elements)
+                }
+                .map_err(error)?
+            };
+        }
+        let color = color(&series.colors[0]);
+        let series_xs = if series.xs.is_empty() {
+            &xs
+        } else {
+            &series.xs
+        };
+        let points: Vec<_> = series_xs
+            .iter()
+            .zip(&series.values)
+            .filter_map(|(x, y)| y.map(|y| (*x, y)))
+            .collect();
+        match series.mark {
+            Mark::Area => {
+                draw_series!(AreaSeries::new(points.iter().copied(), 0.0, color.filled())
+                    .border_style(color.stroke_width(2)));
+            }
+            Mark::Line if series.dashed => {
+                draw_series!(DashedLineSeries::new(
+                    points.iter().copied(),
+                    5,
+                    5,
+                    color.stroke_width(2),
+                ));
+            }
+            Mark::Line => {
+                draw_series!(LineSeries::new(
+                    points.iter().copied(),
+                    color.stroke_width(2),
+                ));
+            }
+            Mark::Point => {
+                draw_series!(points.iter().enumerate().map(|(i, point)| {
+                    let radius = series.radii.get(i).map_or(3, |r| r.round() as u32);
+                    Circle::new(*point, radius, color.mix(0.5).filled())
+                }));
+            }
+            Mark::Bar | Mark::Arc => continue,
+        }
+        if series.points {
+            draw_series!(points
+                .iter()
+                .map(|point| Circle::new(*point, 3, color.filled())));
+        }
+    }
+    Ok(())
+}
+
+fn draw<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    figure: &Figure,
+    title: &str,
+    style: &Style,
+) -> Result<(), String> {
+    register();
+    root.fill(&style.background).map_err(error)?;
+    let area = root
+        .titled(
+            title,
+            ("sans-serif", 20).into_font().color(&style.foreground),
+        )
+        .map_err(error)?;
+
+    if let Some(series) = figure.series.iter().find(|x| x.mark == Mark::Arc) {
+        draw_pie(&area, figure, series, style)?;
+    } else {
+        let (width, _) = area.dim_in_pixel();
+        let (plot, legend) = area.split_horizontally(width.saturating_sub(LEGEND_WIDTH));
+        draw_cartesian(&plot, figure, style)?;
+        let entries: Vec<_> = figure
+            .series
+            .iter()
+            .filter(|series| is_shown(series))
+            .map(|series| (series.name.clone(), color(&series.colors[0])))
+            .collect();
+        draw_legend(&legend, &entries, style)?;
+    }
+    root.present().map_err(error)
+}
+
+/// renders the figure as a svg document.
+pub fn render_svg(figure: &Figure, title: &str, style: &Style) -> Result<String, String> {
+    let mut svg = String::new();
+    {
+        let root =
+            SVGBackend::with_string(&mut svg, (style.width, style.height)).into_drawing_area();
+        draw(&root, figure, title, style)?;
+    }
+    Ok(svg)
+}
+
+/// renders the figure into a png file.
+pub fn render_png(figure: &Figure, title: &str, style: &Style, path: &Path) -> Result<(), String> {
+    let root = BitMapBackend::new(path, (style.width, style.height)).into_drawing_area();
+    draw(&root, figure, title, style)
+}
+
+/// The charts of the dashboard with their default options within the range, by name and title,
+/// the provincial draws are only charted when given.
+pub fn figures(
+    pool_data: &[Pool],
+    invite_data: &[Invite],
+    plan_data: &[Plan],
+    provincial_data: &[ProvincialDraw],
+    range: DateRange,
+) -> Result<Vec<(&'static str, String, Figure)>, String> {
+    let year = range
+        .invites(invite_data)
+        .last()
+        .map(|invite| invite.date.year())
+        .unwrap_or_default();
+    let mut figures = vec![
+        (
+            "invite_score",
+            "Invitation CRS Score".into(),
+            chart::invite::invite_score_data(pool_data, invite_data, "none", false, range)?.into(),
+        ),
+        (
+            "invite_size",
+            "Invitation Size".into(),
+            chart::invite::invite_size_data(pool_data, invite_data, "m", false, range)?.into(),
+        ),
+        (
+            "invite_program",
+            "Invitation CRS Score by Program".into(),
+            chart::invite::invite_program_data(invite_data, range).into(),
+        ),
+        (
+            "invite_bubble",
+            "Invitations by Score and Size".into(),
+            chart::invite::invite_bubble_data(invite_data, range).into(),
+        ),
+        (
+            "cutoff_regression",
+            "Invitation CRS Score Against Size".into(),
+            chart::regression::cutoff_regression_data(pool_data, invite_data, "size", range)?
+                .into(),
+        ),
+        (
+            "pool_count",
+            "Candidates in the Pool".into(),
+            chart::pool::pool_count_data(pool_data, range).into(),
+        ),
+        (
+            "pool_rate",
+            "Candidates Increase Rate".into(),
+            chart::pool::pool_rate_data(pool_data, invite_data, "ema", range)?.into(),
+        ),
+        (
+            "category_invite",
+            "Invitations by Category (%)".into(),
+            chart::category::category_invite_data(pool_data, invite_data, 0, true, range)?.into(),
+        ),
+        (
+            "category_pool",
+            "Candidates Invited by Category (%)".into(),
+            chart::category::category_pool_data(pool_data, invite_data, 0, range)?.into(),
+        ),
+        (
+            "category_composition",
+            "Candidates Eligible by Category".into(),
+            chart::category::category_composition_data(pool_data, invite_data, "all", range)?
+                .into(),
+        ),
+        (
+            "plan_size",
+            "Invitations and IRCC Plan".into(),
+            chart::plan::plan_size_data(pool_data, invite_data, plan_data, range).into(),
+        ),
+        (
+            "plan_pie",
+            format!("IRCC Plan {}", year),
+            chart::plan::plan_pie_data(pool_data, invite_data, plan_data, year, range).into(),
+        ),
+        (
+            "predict",
+            "Predicted CRS Score".into(),
+            chart::plan::predict_data(pool_data, invite_data, plan_data, range).into(),
+        ),
+    ];
+    // the year may have no plan yet
+    if let Ok(chart) =
+        chart::plan::plan_pace_data(pool_data, invite_data, plan_data, year, "linear", range)
+    {
+        figures.push((
+            "plan_pace",
+            format!("IRCC Plan Pace {}", year),
+            chart.into(),
+        ));
+    }
+    if let Ok(chart) =
+        chart::plan::category_progress_data(pool_data, invite_data, plan_data, year, range)
+    {
+        figures.push((
+            "category_progress",
+            format!("Category Targets {}", year),
+            chart.into(),
+        ));
+    }
+    if !provincial_data.is_empty() {
+        figures.push((
+            "provincial_inflow",
+            "Provincial Invitations and PNP Inflow".into(),
+            chart::provincial::provincial_inflow_data(
+                pool_data,
+                invite_data,
+                provincial_data,
+                range,
+            )
+            .into(),
+        ));
+    }
+    Ok(figures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render_png, render_svg, Style};
+    use crate::chart::dataset::{
+        BarDataset, BubbleDataset, BubblePoint, ChartData, Label, LineDataset, PieDataset, Tooltip,
+    };
+    use crate::chart::figure::Figure;
+
+    // set `UPDATE_GOLDEN` to rewrite the golden files after a deliberate change
+    fn golden(name: &str, figure: &Figure) {
+        let svg = render_svg(figure, name, &Style::LIGHT).unwrap();
+        let path = format!(
+            "{}/src/render/golden/{}.svg",
+            env!("CARGO_MANIFEST_DIR"),
+            name
+        );
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::write(&path, &svg).unwrap();
+        }
+        let expected = std::fs::read_to_string(&path).unwrap();
+        assert!(svg == expected, "{} differs from {}", name, path);
+    }
+
+    fn lines() -> Figure {
+        let day = 86400000.0;
+        ChartData {
+            labels: (0..4)
+                .map(|i| Label::from(19723.0 * day + 14.0 * day * i as f64))
+                .collect(),
+            datasets: vec![
+                LineDataset {
+                    label: Some("CEC".into()),
+                    data: vec![Some(520.0), Some(510.0), None, Some(500.0)],
+                    border_color: "#E74C3C".into(),
+                    ..Default::default()
+                },
+                LineDataset {
+                    label: Some("Pool".into()),
+                    data: vec![Some(480.0), Some(490.0), Some(495.0), Some(505.0)],
+                    border_color: "#3498DB".into(),
+                    fill: true,
+                    ..Default::default()
+                },
+            ],
+            tooltip: Tooltip::default(),
+        }
+        .into()
+    }
+
+    fn bars() -> Figure {
+        ChartData {
+            labels: vec![Label::from("2024-01"), Label::from("2024-02")],
+            datasets: vec![
+                BarDataset {
+                    label: Some("CEC".into()),
+                    data: vec![Some(1500.0), Some(2000.0)],
+                    background_color: "#E74C3C".into(),
+                    ..Default::default()
+                },
+                BarDataset {
+                    label: Some("PNP".into()),
+                    data: vec![Some(800.0), None],
+                    background_color: "#9B59B6".into(),
+                    ..Default::default()
+                },
+            ],
+            tooltip: Tooltip::default(),
+        }
+        .into()
+    }
+
+    fn pie() -> Figure {
+        ChartData {
+            labels: vec![Label::from("Used"), Label::from("Unused")],
+            datasets: vec![PieDataset {
+                data: vec![1.0, 3.0],
+                background_color: vec!["#58D68D".into(), "#F4D03F".into()],
+                ..Default::default()
+            }],
+            tooltip: Tooltip::default(),
+        }
+        .into()
+    }
+
+    fn bubbles() -> Figure {
+        let day = 86400000.0;
+        let bubble = |i: f64, y, r| BubblePoint {
+            x: 19723.0 * day + 14.0 * day * i,
+            y,
+            r,
+        };
+        ChartData {
+            labels: Vec::new(),
+            datasets: vec![
+                BubbleDataset {
+                    label: Some("General".into()),
+                    data: vec![bubble(0.0, 520.0, 20.0), bubble(2.0, 500.0, 10.0)],
+                    border_color: "#E74C3C".into(),
+                    ..Default::default()
+                },
+                BubbleDataset {
+                    label: Some("French".into()),
+                    data: vec![bubble(1.0, 420.0, 8.0)],
+                    border_color: "#3498DB".into(),
+                    ..Default::default()
+                },
+            ],
+            tooltip: Tooltip::default(),
+        }
+        .into()
+    }
+
+    #[tokio::test]
+    async fn svg() {
+        golden("lines", &lines());
+        golden("bars", &bars());
+        golden("pie", &pie());
+        golden("bubbles", &bubbles());
+    }
+
+    #[tokio::test]
+    async fn png() {
+        let path = std::env::temp_dir().join("analyzer-render-test.png");
+        render_png(&bars(), "bars", &Style::DARK, &path).unwrap();
+        let png = std::fs::read(&path).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,381 @@
+use crate::chart;
+use crate::chart::dataset::ChartData;
+use crate::chart::figure::Figure;
+use crate::chart::vega::vega_lite;
+use crate::data::{CategoryCode, DataSource, DateRange, Invite, Plan, Pool};
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::routing::get;
+use axum::{Json, Router};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::future::IntoFuture;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use tokio::net::{TcpListener, ToSocketAddrs};
+
+type Shared = Arc<RwLock<Option<Arc<Data>>>>;
+type Response = Result<Json<serde_json::Value>, (StatusCode, String)>;
+
+#[derive(Debug)]
+struct Data {
+    invite_data: Vec<Invite>,
+    pool_data: Vec<Pool>,
+    plan_data: Vec<Plan>,
+}
+
+/// Query parameters shared by every endpoint, each endpoint ignores the ones it has no use of.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ChartQuery {
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    /// a preset of `DateRange`, overridden by `from` and `to`.
+    range: Option<String>,
+    category: Option<String>,
+    mode: Option<String>,
+    method: Option<String>,
+    split_pnp: bool,
+    with_pnp: Option<bool>,
+    year: Option<i32>,
+    /// shape of the plan pace, "linear" (default) or "historical".
+    shape: Option<String>,
+    format: Option<String>,
+}
+
+impl ChartQuery {
+    fn category(&self) -> Result<Option<CategoryCode>, (StatusCode, String)> {
+        match &self.category {
+            None => Ok(None),
+            Some(category) => CategoryCode::values()
+                .iter()
+                .find(|code| code.as_str().eq_ignore_ascii_case(category))
+                .map(|code| Some(*code))
+                .ok_or_else(|| bad_request(format!("invalid category {}", category))),
+        }
+    }
+
+    // the producers restrict the data themselves, with the history their analysis needs
+    fn range(&self, data: &Data) -> Result<DateRange, (StatusCode, String)> {
+        let preset = match &self.range {
+            Some(preset) => DateRange::preset(preset, &data.invite_data).map_err(bad_request)?,
+            None => DateRange::ALL,
+        };
+        let range = DateRange::new(self.from.or(preset.from), self.to.or(preset.to));
+
+        if range.invites(&data.invite_data).is_empty() || range.pools(&data.pool_data).is_empty() {
+            Err(bad_request("no data within the date range".into()))
+        } else {
+            Ok(range)
+        }
+    }
+}
+
+fn bad_request(message: String) -> (StatusCode, String) {
+    (StatusCode::BAD_REQUEST, message)
+}
+
+fn json(value: impl Serialize) -> Response {
+    serde_json::to_value(value)
+        .map(Json)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))
+}
+
+/// `format` is "chartjs" (default) or "vega-lite".
+fn render<T: Serialize>(query: &ChartQuery, chart: ChartData<T>) -> Response
+where
+    Figure: From<ChartData<T>>,
+{
+    match query.format.as_deref().unwrap_or("chartjs") {
+        "chartjs" => json(chart),
+        "vega-lite" => json(vega_lite(&chart.into())),
+        format => Err(bad_request(format!("invalid format {}", format))),
+    }
+}
+
+fn data(
+    state: &Shared,
+    query: &ChartQuery,
+) -> Result<(Arc<Data>, DateRange), (StatusCode, String)> {
+    match &*state.read().unwrap() {
+        Some(data) => Ok((Arc::clone(data), query.range(data)?)),
+        None => Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            "data are not fetched yet".into(),
+        )),
+    }
+}
+
+/// `mode` is "score" (default), "program" (the cutoffs per program), "bubble" (the draws sized
+/// by their invitations) or a granularity of the invitation size ("d", "w", "m", "q", "y", "fy"
+/// or "r<days>").
+async fn draws(State(state): State<Shared>, Query(query): Query<ChartQuery>) -> Response {
+    let (data, range) = data(&state, &query)?;
+    // the other draws are still analyzed, e.g. to split the PNP invitations or count the year to
+    // date, only the datasets of the category are kept
+    let category = query.category()?;
+    let label = category.map(|category| category.as_str());
+    let method = query.method.as_deref().unwrap_or("none");
+
+    match query.mode.as_deref().unwrap_or("score") {
+        "score" => {
+            let chart = chart::invite::invite_score_data(
+                &data.pool_data,
+                &data.invite_data,
+                method,
+                query.split_pnp,
+                range,
+            )
+            .map_err(bad_request)?;
+            match &label {
+                Some(label) => render(&query, chart.retain_label(label)),
+                None => render(&query, chart),
+            }
+        }
+        // the datasets are the programs, only the draws of the category are charted
+        "program" => {
+            let invite_data: Vec<_> = data
+                .invite_data
+                .iter()
+                .filter(|invite| category.is_none_or(|category| invite.category.code == category))
+                .cloned()
+                .collect();
+            render(
+                &query,
+                chart::invite::invite_program_data(&invite_data, range),
+            )
+        }
+        "bubble" => {
+            let chart = chart::invite::invite_bubble_data(&data.invite_data, range);
+            match &label {
+                Some(label) => render(&query, chart.retain_label(label)),
+                None => render(&query, chart),
+            }
+        }
+        mode => {
+            let chart = chart::invite::invite_size_data(
+                &data.pool_data,
+                &data.invite_data,
+                mode,
+                query.split_pnp,
+                range,
+            )
+            .map_err(bad_request)?;
+            match &label {
+                Some(label) => render(&query, chart.retain_label(label)),
+                None => render(&query, chart),
+            }
+        }
+    }
+}
+
+async fn pool(State(state): State<Shared>, Query(query): Query<ChartQuery>) -> Response {
+    let (data, range) = data(&state, &query)?;
+    render(&query, chart::pool::pool_count_data(&data.pool_data, range))
+}
+
+async fn rate(State(state): State<Shared>, Query(query): Query<ChartQuery>) -> Response {
+    let (data, range) = data(&state, &query)?;
+    let method = query.method.as_deref().unwrap_or("ema");
+    render(
+        &query,
+        chart::pool::pool_rate_data(&data.pool_data, &data.invite_data, method, range)
+            .map_err(bad_request)?,
+    )
+}
+
+/// `mode` is "invite" (default), "pool" or "composition", `year` is a category year, 0 (default)
+/// for all, the composition of a single `category` comes with the bounds of its estimate.
+async fn categories(State(state): State<Shared>, Query(query): Query<ChartQuery>) -> Response {
+    let (data, range) = data(&state, &query)?;
+    if query.mode.as_deref() == Some("composition") {
+        let category = match query.category()? {
+            Some(category) => category.as_str(),
+            None => "all".into(),
+        };
+        let chart = chart::category::category_composition_data(
+            &data.pool_data,
+            &data.invite_data,
+            &category,
+            range,
+        )
+        .map_err(bad_request)?;
+        return render(&query, chart);
+    }
+    let year = query.year.unwrap_or(0);
+    let chart = match query.mode.as_deref().unwrap_or("invite") {
+        "invite" => chart::category::category_invite_data(
+            &data.pool_data,
+            &data.invite_data,
+            year,
+            query.with_pnp.unwrap_or(true),
+            range,
+        ),
+        "pool" => {
+            chart::category::category_pool_data(&data.pool_data, &data.invite_data, year, range)
+        }
+        mode => Err(format!("invalid mode {}", mode)),
+    }
+    .map_err(bad_request)?;
+
+    match query.category()? {
+        Some(category) => render(&query, chart.retain_label(&category.as_str())),
+        None => render(&query, chart),
+    }
+}
+
+async fn predict(State(state): State<Shared>, Query(query): Query<ChartQuery>) -> Response {
+    let (data, range) = data(&state, &query)?;
+    let chart =
+        chart::plan::predict_data(&data.pool_data, &data.invite_data, &data.plan_data, range);
+    match query.category()? {
+        Some(category) => render(&query, chart.retain_label(&category.as_str())),
+        None => render(&query, chart),
+    }
+}
+
+/// `mode` is "size" (default), "pie", "pace", "progress" or "targets" (per category), which
+/// require `year`.
+async fn plan(State(state): State<Shared>, Query(query): Query<ChartQuery>) -> Response {
+    let (data, range) = data(&state, &query)?;
+    match (query.mode.as_deref().unwrap_or("size"), query.year) {
+        ("size", _) => render(
+            &query,
+            chart::plan::plan_size_data(&data.pool_data, &data.invite_data, &data.plan_data, range),
+        ),
+        ("pie", Some(year)) => render(
+            &query,
+            chart::plan::plan_pie_data(
+                &data.pool_data,
+                &data.invite_data,
+                &data.plan_data,
+                year,
+                range,
+            ),
+        ),
+        ("pace", Some(year)) => render(
+            &query,
+            chart::plan::plan_pace_data(
+                &data.pool_data,
+                &data.invite_data,
+                &data.plan_data,
+                year,
+                query.shape.as_deref().unwrap_or("linear"),
+                range,
+            )
+            .map_err(bad_request)?,
+        ),
+        ("progress", Some(year)) => render(
+            &query,
+            chart::plan::category_progress_data(
+                &data.pool_data,
+                &data.invite_data,
+                &data.plan_data,
+                year,
+                range,
+            )
+            .map_err(bad_request)?,
+        ),
+        ("targets", Some(year)) => render(
+            &query,
+            chart::plan::category_target_data(
+                &data.pool_data,
+                &data.invite_data,
+                &data.plan_data,
+                year,
+                range,
+            )
+            .map_err(bad_request)?,
+        ),
+        ("pie" | "pace" | "progress" | "targets", None) => {
+            Err(bad_request("year is required".into()))
+        }
+        (mode, _) => Err(bad_request(format!("invalid mode {}", mode))),
+    }
+}
+
+async fn refresh<S: DataSource>(source: S, state: Shared, interval: Duration) {
+    loop {
+        match source.load().await {
+            Ok((invite_data, pool_data, plan_data)) => {
+                let data = Data {
+                    invite_data,
+                    pool_data,
+                    plan_data,
+                };
+                *state.write().unwrap() = Some(Arc::new(data));
+            }
+            Err(e) => eprintln!("{}", e),
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// Serves the chart data as json, the data are loaded again from `source` every `interval`.
+pub async fn serve<S: DataSource>(
+    addr: impl ToSocketAddrs,
+    source: S,
+    interval: Duration,
+) -> std::io::Result<()> {
+    let state: Shared = Arc::new(RwLock::new(None));
+    let app = Router::new()
+        .route("/draws", get(draws))
+        .route("/pool", get(pool))
+        .route("/rate", get(rate))
+        .route("/categories", get(categories))
+        .route("/predict", get(predict))
+        .route("/plan", get(plan))
+        .with_state(state.clone());
+    let listener = TcpListener::bind(addr).await?;
+
+    tokio::select! {
+        result = axum::serve(listener, app).into_future() => result,
+        _ = refresh(source, state, interval) => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChartQuery, Data};
+    use crate::data::test_util::{invites, pools};
+    use crate::data::{CategoryCode, Plan};
+    use chrono::NaiveDate;
+
+    #[tokio::test]
+    async fn query() {
+        let query: ChartQuery = serde_json::from_value(
+            serde_json::json!({ "from": "2024-01-01", "range": "1y", "category": "stem" }),
+        )
+        .unwrap();
+        assert_eq!(query.category().unwrap(), Some(CategoryCode::Stem));
+
+        let date = |month| NaiveDate::from_ymd_opt(2024, month, 1).unwrap();
+        let data = Data {
+            invite_data: Vec::new(),
+            pool_data: pools(&[(date(2), 1000.0)]),
+            plan_data: vec![Plan::new(2023, 1.0, 2.0), Plan::new(2024, 1.0, 2.0)],
+        };
+        assert!(query.range(&data).is_err());
+
+        let data = Data {
+            invite_data: invites(&[(date(3), "General", 1500.0, 500.0)]),
+            ..data
+        };
+        // `from` overrides the start of the preset
+        let range = query.range(&data).unwrap();
+        assert_eq!(range.from, Some(date(1)));
+        assert_eq!(range.to, None);
+
+        let query = ChartQuery {
+            category: Some("unknown".into()),
+            ..Default::default()
+        };
+        assert!(query.category().is_err());
+
+        let query = ChartQuery {
+            range: Some("10y".into()),
+            ..Default::default()
+        };
+        assert!(query.range(&data).is_err());
+    }
+}
@@ -0,0 +1,383 @@
+mod notify;
+
+use crate::analyze::anomaly::{Anomaly, AnomalyAnalyzer};
+use crate::analyze::config::AnalysisConfig;
+use crate::analyze::predict::Predictor;
+use crate::data::{CategoryCode, DataSource, Invite, InviteId, Plan, Pool};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+pub use notify::Notifier;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DrawDelta {
+    pub previous_id: String,
+    pub days: i64,
+    pub size: f64,
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewDraw {
+    pub id: String,
+    pub date: NaiveDate,
+    pub category: CategoryCode,
+    pub size: f64,
+    pub score: f64,
+    /// compared with the previous draw of the same category.
+    pub delta: Option<DrawDelta>,
+    /// compared with the history of the category.
+    pub anomalies: Vec<Anomaly>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewPool {
+    pub date: NaiveDate,
+    pub total: f64,
+    /// score range and number of candidates, from the highest scores.
+    pub counts: Vec<(String, f64)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PredictedScore {
+    pub date: NaiveDate,
+    pub category: CategoryCode,
+    pub score: f64,
+}
+
+/// Payload sent to the notifiers.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Notification {
+    pub draws: Vec<NewDraw>,
+    pub pool: Option<NewPool>,
+    /// scores of the next predicted draw, with all the data including the new ones.
+    pub prediction: Vec<PredictedScore>,
+}
+
+impl Notification {
+    pub fn subject(&self) -> String {
+        match self.draws.last() {
+            Some(draw) => format!("Express Entry draw {} ({})", draw.id, draw.date),
+            None => "Express Entry pool distribution updated".into(),
+        }
+    }
+}
+
+impl fmt::Display for Notification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for draw in &self.draws {
+            write!(
+                f,
+                "Draw {} on {}: {} {} ITAs, CRS {}",
+                draw.id,
+                draw.date,
+                draw.size,
+                draw.category.as_str(),
+                draw.score
+            )?;
+            if let Some(delta) = &draw.delta {
+                write!(
+                    f,
+                    " ({:+} ITAs, {:+} CRS, {} days after draw {})",
+                    delta.size, delta.score, delta.days, delta.previous_id
+                )?;
+            }
+            writeln!(f)?;
+            for anomaly in &draw.anomalies {
+                writeln!(
+                    f,
+                    "  Anomaly ({}): {}",
+                    anomaly.kind.as_str(),
+                    anomaly.explanation
+                )?;
+            }
+        }
+        if let Some(pool) = &self.pool {
+            writeln!(f, "Pool as on {}: {} candidates", pool.date, pool.total)?;
+        }
+        for prediction in &self.prediction {
+            writeln!(
+                f,
+                "Predicted {} on {}: CRS {:.0}",
+                prediction.category.as_str(),
+                prediction.date,
+                prediction.score
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Content of the json given to the `watch` binary.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WatchConfig {
+    #[serde(default = "WatchConfig::default_interval_secs")]
+    pub interval_secs: u64,
+    /// url or path of the IRCC json, or `sqlite:<db>` for the tables of a database, the IRCC
+    /// website if absent.
+    pub source: Option<String>,
+    /// last known draw, only the draws after it are notified on the first poll.
+    pub since: Option<String>,
+    pub notifiers: Vec<Notifier>,
+}
+
+impl WatchConfig {
+    fn default_interval_secs() -> u64 {
+        300
+    }
+}
+
+/// Polls a `DataSource` and notifies new draws and pool distributions.
+pub struct Watcher<S> {
+    source: S,
+    interval: Duration,
+    notifiers: Vec<Notifier>,
+    last_invite: Option<InviteId>,
+    last_pool: Option<NaiveDate>,
+}
+
+impl<S: DataSource> Watcher<S> {
+    pub fn new(source: S, interval: Duration) -> Self {
+        Self {
+            source,
+            interval,
+            notifiers: Vec::new(),
+            last_invite: None,
+            last_pool: None,
+        }
+    }
+
+    pub fn notifier(mut self, notifier: Notifier) -> Self {
+        self.notifiers.push(notifier);
+        self
+    }
+
+    pub fn since(mut self, id: InviteId) -> Self {
+        self.last_invite = Some(id);
+        self
+    }
+
+    /// fetches once, the first poll only records the current state unless `since` is set.
+    pub async fn poll(&mut self) -> Result<Option<Notification>, String> {
+        let (invite_data, pool_data, plan_data) = self.source.load().await?;
+
+        let draws: Vec<_> = match self.last_invite {
+            Some(last_invite) => invite_data
+                .iter()
+                .enumerate()
+                .filter(|(_, invite)| invite.id > last_invite)
+                .map(|(i, invite)| Self::new_draw(&invite_data[..i], invite))
+                .collect(),
+            None => Vec::new(),
+        };
+        let pool = match (self.last_pool, pool_data.last()) {
+            (Some(last_pool), Some(pool)) if pool.date > last_pool => Some(Self::new_pool(pool)),
+            _ => None,
+        };
+
+        self.last_invite = invite_data
+            .last()
+            .map(|invite| invite.id)
+            .or(self.last_invite);
+        self.last_pool = pool_data.last().map(|pool| pool.date).or(self.last_pool);
+
+        if draws.is_empty() && pool.is_none() {
+            return Ok(None);
+        }
+        let prediction = Self::prediction(&pool_data, &invite_data, &plan_data);
+        Ok(Some(Notification {
+            draws,
+            pool,
+            prediction,
+        }))
+    }
+
+    /// polls forever, failures are reported on stderr and do not stop the watcher.
+    pub async fn run(mut self) {
+        loop {
+            match self.poll().await {
+                Ok(Some(notification)) => {
+                    for notifier in &self.notifiers {
+                        if let Err(e) = notifier.notify(&notification).await {
+                            eprintln!("{}", e);
+                        }
+                    }
+                }
+                Ok(None) => (),
+                Err(e) => eprintln!("{}", e),
+            }
+            tokio::time::sleep(self.interval).await;
+        }
+    }
+
+    fn new_draw(previous: &[Invite], invite: &Invite) -> NewDraw {
+        let delta = previous
+            .iter()
+            .rev()
+            .find(|x| x.category.code == invite.category.code)
+            .map(|x| DrawDelta {
+                previous_id: x.id.to_string(),
+                days: (invite.date - x.date).num_days(),
+                size: invite.size - x.size,
+                score: invite.score - x.score,
+            });
+
+        NewDraw {
+            id: invite.id.to_string(),
+            date: invite.date,
+            category: invite.category.code,
+            size: invite.size,
+            score: invite.score,
+            delta,
+            anomalies: AnomalyAnalyzer::anomalies(&AnalysisConfig::current(), previous, invite),
+        }
+    }
+
+    fn new_pool(pool: &Pool) -> NewPool {
+        NewPool {
+            date: pool.date,
+            total: pool.total(),
+            counts: (0..Pool::N)
+                .map(|i| (Pool::as_str(i), pool.count(i)))
+                .collect(),
+        }
+    }
+
+    fn prediction(
+        pool_data: &[Pool],
+        invite_data: &[Invite],
+        plan_data: &[Plan],
+    ) -> Vec<PredictedScore> {
+        Predictor::upcoming(
+            &AnalysisConfig::current(),
+            pool_data,
+            invite_data,
+            plan_data,
+            1,
+        )
+        .into_iter()
+        .map(|(date, category, score)| PredictedScore {
+            date,
+            category,
+            score,
+        })
+        .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Watcher;
+    use crate::analyze::anomaly::AnomalyKind;
+    use crate::data::{DrawSource, EeRounds123En, RawData};
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    struct Snapshots(Mutex<Vec<EeRounds123En>>);
+
+    impl DrawSource for Snapshots {
+        async fn fetch(&self) -> Result<EeRounds123En, String> {
+            let mut snapshots = self.0.lock().unwrap();
+            match snapshots.len() {
+                0 => Err("no more snapshots".into()),
+                _ => Ok(snapshots.remove(0)),
+            }
+        }
+    }
+
+    fn round(draw_number: &str, date: &str, name: &str, size: &str, score: &str) -> RawData {
+        serde_json::from_value(serde_json::json!({
+            "DrawText1": "", "dd1": "1,000", "dd2": "2,000", "dd3": "0", "dd4": "3,000",
+            "dd5": "4,000", "dd6": "5,000", "dd7": "6,000", "dd8": "7,000", "dd9": "0",
+            "dd10": "8,000", "dd11": "9,000", "dd12": "10,000", "dd13": "11,000",
+            "dd14": "12,000", "dd15": "13,000", "dd16": "14,000", "dd17": "15,000",
+            "dd18": "0", "drawCRS": score, "drawCutOff": "", "drawDate": "",
+            "drawDateFull": date, "drawDateTime": "", "drawDistributionAsOn": date,
+            "drawName": name, "drawNumber": draw_number, "drawNumberURL": "",
+            "drawSize": size, "mitext": "",
+            "drawText2": "Federal Skilled Worker, Canadian Experience Class, Federal Skilled Trades and Provincial Nominee Program",
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn poll() {
+        let old = vec![
+            round("1", "January 10, 2024", "General", "1,500", "540"),
+            round(
+                "2",
+                "January 24, 2024",
+                "Provincial Nominee Program",
+                "800",
+                "720",
+            ),
+        ];
+        let mut new = old.clone();
+        new.push(round("3", "February 7, 2024", "General", "1,200", "530"));
+        let snapshot = |rounds: Vec<RawData>| EeRounds123En {
+            classes: "".into(),
+            rounds: rounds.into_iter().rev().collect(),
+        };
+
+        let source = Snapshots(Mutex::new(vec![snapshot(old.clone()), snapshot(new)]));
+        let mut watcher = Watcher::new(source, Duration::ZERO);
+        assert_eq!(watcher.poll().await.unwrap(), None);
+
+        let notification = watcher.poll().await.unwrap().unwrap();
+        assert_eq!(notification.draws.len(), 1);
+        assert_eq!(notification.draws[0].id, "3");
+        let delta = notification.draws[0].delta.as_ref().unwrap();
+        assert_eq!(delta.previous_id, "1");
+        assert_eq!(delta.days, 28);
+        assert_eq!(delta.size, -300.0);
+        assert_eq!(delta.score, -10.0);
+        assert_eq!(
+            notification.pool.map(|pool| pool.date.to_string()),
+            Some("2024-02-07".into())
+        );
+
+        assert!(watcher.poll().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn anomalies() {
+        let old = vec![round("1", "January 10, 2024", "General", "1,500", "540")];
+        let mut new = old.clone();
+        new.push(round("2", "January 24, 2024", "General", "1,200", "530"));
+        new.push(round(
+            "3",
+            "January 25, 2024",
+            "STEM occupations (2024-1)",
+            "500",
+            "480",
+        ));
+        let snapshot = |rounds: Vec<RawData>| EeRounds123En {
+            classes: "".into(),
+            rounds: rounds.into_iter().rev().collect(),
+        };
+
+        let source = Snapshots(Mutex::new(vec![snapshot(old), snapshot(new)]));
+        let mut watcher = Watcher::new(source, Duration::ZERO);
+        assert_eq!(watcher.poll().await.unwrap(), None);
+
+        let notification = watcher.poll().await.unwrap().unwrap();
+        assert!(notification.draws[0].anomalies.is_empty());
+        let anomalies = &notification.draws[1].anomalies;
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].kind, AnomalyKind::FirstDraw);
+        assert_eq!(anomalies[0].explanation, "no earlier STEM draw");
+        assert!(notification
+            .to_string()
+            .contains("  Anomaly (first draw): no earlier STEM draw\n"));
+        let payload = serde_json::to_value(&notification).unwrap();
+        assert_eq!(payload["draws"][1]["anomalies"][0]["kind"], "firstDraw");
+    }
+}
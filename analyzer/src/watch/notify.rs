@@ -0,0 +1,116 @@
+use super::Notification;
+use lettre::message::{header::ContentType, Mailbox};
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::Deserialize;
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Notifier {
+    /// prints the notification as text.
+    Stdout,
+    /// POSTs the notification as json.
+    Webhook { url: String },
+    /// sends the notification as text through a relay without authentication nor TLS.
+    Smtp {
+        relay: String,
+        #[serde(default = "Notifier::default_smtp_port")]
+        port: u16,
+        from: String,
+        to: Vec<String>,
+    },
+    /// runs the command with the notification as json on its stdin.
+    Exec {
+        command: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+}
+
+impl Notifier {
+    fn default_smtp_port() -> u16 {
+        25
+    }
+
+    pub async fn notify(&self, notification: &Notification) -> Result<(), String> {
+        match self {
+            Self::Stdout => {
+                println!("{}", notification.subject());
+                print!("{}", notification);
+                Ok(())
+            }
+            Self::Webhook { url } => reqwest::Client::new()
+                .post(url)
+                .json(notification)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map(|_| ())
+                .map_err(|e| format!("webhook {} failed: {}", url, e)),
+            Self::Smtp {
+                relay,
+                port,
+                from,
+                to,
+            } => Self::send_mail(relay, *port, from, to, notification)
+                .await
+                .map_err(|e| format!("smtp {} failed: {}", relay, e)),
+            Self::Exec { command, args } => Self::exec(command, args, notification)
+                .await
+                .map_err(|e| format!("exec {} failed: {}", command, e)),
+        }
+    }
+
+    async fn send_mail(
+        relay: &str,
+        port: u16,
+        from: &str,
+        to: &[String],
+        notification: &Notification,
+    ) -> Result<(), String> {
+        let mut builder = Message::builder()
+            .from(from.parse::<Mailbox>().map_err(|e| e.to_string())?)
+            .subject(notification.subject())
+            .header(ContentType::TEXT_PLAIN);
+        for to in to {
+            builder = builder.to(to.parse::<Mailbox>().map_err(|e| e.to_string())?);
+        }
+        let message = builder
+            .body(notification.to_string())
+            .map_err(|e| e.to_string())?;
+
+        AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(relay)
+            .port(port)
+            .build()
+            .send(message)
+            .await
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    async fn exec(
+        command: &str,
+        args: &[String],
+        notification: &Notification,
+    ) -> Result<(), String> {
+        let payload = serde_json::to_vec(notification).map_err(|e| e.to_string())?;
+        let mut child = Command::new(command)
+            .args(args)
+            .stdin(Stdio::piped())
+            .spawn()
+            .map_err(|e| e.to_string())?;
+
+        let mut stdin = child.stdin.take().unwrap();
+        stdin.write_all(&payload).await.map_err(|e| e.to_string())?;
+        drop(stdin);
+
+        let status = child.wait().await.map_err(|e| e.to_string())?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(status.to_string())
+        }
+    }
+}
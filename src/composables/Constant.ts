@@ -57,6 +57,62 @@ export function useCategoryColor(str: DrawCategory): string {
     }
 }
 
+export const SmoothMethods = [
+    { label: "No Smoothing", key: "none" },
+    { label: "Exponential", key: "ema" },
+    { label: "Moving Average", key: "ma" },
+    { label: "LOESS", key: "loess" },
+    { label: "Savitzky-Golay", key: "sg" },
+    { label: "Kalman", key: "kalman" },
+];
+
+// granularities of the invitation size chart besides the ones following the zoom,
+// see `Granularity::parse` of the analyzer
+export const SizeGranularities = [
+    { label: "Auto", key: "auto" },
+    { label: "Quarterly", key: "q" },
+    { label: "Yearly", key: "y" },
+    { label: "Fiscal Year (Apr - Mar)", key: "fy" },
+    { label: "Rolling 30 Days", key: "r30" },
+    { label: "Rolling 90 Days", key: "r90" },
+];
+
+// variables the cutoffs are plotted against, see `CutoffVariable::parse` of the analyzer
+export const CutoffVariables = [
+    { label: "Draw Size", key: "size" },
+    { label: "Days Since Previous Draw", key: "gap" },
+    { label: "Candidates Above 500", key: "pool" },
+];
+
+// categories of the eligible candidates chart, by the names of `CategoryCode::as_str` of the
+// analyzer
+export const EligibleCategories = [
+    { label: "All", key: "all" },
+    { label: "CEC", key: "CEC" },
+    { label: "FSW", key: "FSW" },
+    { label: "STEM", key: "STEM" },
+    { label: "Health", key: "Health" },
+    { label: "French", key: "French" },
+    { label: "Trade", key: "Trade" },
+    { label: "Transport", key: "Transport" },
+    { label: "Agriculture", key: "Agriculture" },
+];
+
+// shapes of the pace of the plan, see `PaceShape::parse` of the analyzer
+export const PaceShapes = [
+    { label: "Linear Pace", key: "linear" },
+    { label: "Historical Pace", key: "historical" },
+];
+
+// presets of the date range of the charts, see `DateRange::preset` of the analyzer
+export const DateRanges = [
+    { label: "All", key: "all" },
+    { label: "Last 12 Months", key: "1y" },
+    { label: "Last 2 Years", key: "2y" },
+    { label: "Last 5 Years", key: "5y" },
+    { label: "Since Categories", key: "categories" },
+];
+
 export let IrccPlan = {
     2015: { min: 68000, max: 74000 },
     2016: { min: 54000, max: 59000 },
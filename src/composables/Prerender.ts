@@ -0,0 +1,64 @@
+import { ref } from "vue";
+import wasm_init, {
+    wasm_pool_data,
+    wasm_invite_data,
+    wasm_plan_data,
+    wasm_date_range,
+} from "analyzer";
+import { DateRanges } from "./Constant";
+
+export interface Analyzer {
+    poolData: number;
+    inviteData: number;
+    planData: number;
+}
+
+let analyzer: Promise<Analyzer> | null = null;
+let live: Analyzer | null = null;
+
+/** date range of every chart, the pre-rendered charts cover all the data */
+export const dateRange = ref(DateRanges[0]);
+
+/** loads the analyzer and the IRCC data, once for every component */
+export function useAnalyzer(): Promise<Analyzer> {
+    if (analyzer == null) {
+        analyzer = (async function () {
+            await wasm_init();
+            live = {
+                poolData: await wasm_pool_data(),
+                inviteData: await wasm_invite_data(),
+                planData: await wasm_plan_data(),
+            };
+            return live;
+        })();
+    }
+    return analyzer;
+}
+
+async function fetchPrerendered(name: string): Promise<any> {
+    // written by the `prerender` binary of the analyzer at build time
+    const response = await fetch(`charts/${name}.json`);
+    return response.ok ? response.json() : null;
+}
+
+/**
+ * the chart computed by the analyzer once it is loaded, the pre-rendered one until then,
+ * `args` are the arguments of the chart in the order of the `prerender` binary,
+ * `compute` is given the date range to pass to the analyzer
+ */
+export async function useChartData(
+    chart: string,
+    args: Array<string | number | boolean>,
+    compute: (analyzer: Analyzer, range: any) => any
+): Promise<any> {
+    const preset = dateRange.value.key;
+    if (live == null && preset == "all") {
+        const name = [chart, ...args].join("-");
+        const data = await fetchPrerendered(name).catch(() => null);
+        if (data != null) {
+            return data;
+        }
+    }
+    const analyzer = await useAnalyzer();
+    return compute(analyzer, wasm_date_range(analyzer.inviteData, preset));
+}
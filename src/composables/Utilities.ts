@@ -1,3 +1,17 @@
 export function castDateToString(date: Date): string {
     return date.toISOString().substring(0, 10);
 }
+
+/** sets the bounds of the x axis of a chart and of its zoom, then redraws it if it is shown */
+export function setXBounds(chart: any, options: any, min: number, max: number) {
+    if ("min" in options.scales.x) {
+        options.scales.x.min = min;
+        options.scales.x.max = max;
+    }
+    options.plugins.zoom.limits.x = { min: min, max: max };
+    if (chart) {
+        chart.resetZoom();
+        chart.options = options;
+        chart.update("none");
+    }
+}